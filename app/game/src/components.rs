@@ -3,6 +3,7 @@
 //! of the project.
 
 pub mod asteroid;
+pub mod bag;
 pub mod energy_cell;
 pub mod planet;
 pub mod resource;
@@ -24,4 +24,16 @@ impl Asteroid {
     pub(crate) fn new() -> Asteroid {
         Asteroid { _private: () }
     }
+
+    /// # Test utilities - Do not use in production code
+    ///
+    /// Creates a new instance of [Asteroid] from outside this crate, to build the
+    /// protocol messages in tests.
+    ///
+    /// Only available with the `test-utils` feature.
+    #[cfg(feature = "test-utils")]
+    #[must_use]
+    pub fn for_test() -> Asteroid {
+        Self::new()
+    }
 }
@@ -0,0 +1,131 @@
+//! Bag module
+//!
+//! This module defines the [`BagView`] trait, a read-only view over the content of an
+//! explorer bag, and [`ResourceBag`], a simple bag implementation that groups can use
+//! (or take inspiration from) for their explorers.
+//!
+//! The bag content sent with
+//! [`ExplorerToOrchestrator::BagContentResponse`](crate::protocols::orchestrator_explorer::ExplorerToOrchestrator::BagContentResponse)
+//! is generic, so each group is free to use its own bag type: implementing [`BagView`] for it
+//! lets tools (e.g. a GUI) read the content of any group's bag.
+
+use crate::components::resource::{
+    BasicResourceType, ComplexResourceType, GenericResource, ResourceType,
+};
+use std::collections::HashMap;
+use std::slice::Iter;
+
+/// A read-only view over the content of a bag, counting the resources it holds by type.
+pub trait BagView {
+    /// Returns the number of basic resources held in the bag, grouped by type.
+    /// Types with no resources may be omitted.
+    fn basic_counts(&self) -> HashMap<BasicResourceType, u32>;
+
+    /// Returns the number of complex resources held in the bag, grouped by type.
+    /// Types with no resources may be omitted.
+    fn complex_counts(&self) -> HashMap<ComplexResourceType, u32>;
+}
+
+/// A simple bag holding any kind of [`GenericResource`].
+#[derive(Debug, Default)]
+pub struct ResourceBag {
+    resources: Vec<GenericResource>,
+}
+
+impl ResourceBag {
+    /// Creates a new, empty `ResourceBag`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts a resource in the bag.
+    pub fn insert(&mut self, resource: GenericResource) {
+        self.resources.push(resource);
+    }
+
+    /// Takes the first resource of the given type out of the bag,
+    /// or returns `None` if there isn't any.
+    pub fn take(&mut self, resource_type: ResourceType) -> Option<GenericResource> {
+        let idx = self
+            .resources
+            .iter()
+            .position(|res| res.get_type() == resource_type)?;
+        Some(self.resources.remove(idx))
+    }
+
+    /// Returns the number of resources in the bag.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.resources.len()
+    }
+
+    /// Returns `true` if the bag holds no resources.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+
+    /// Returns an *immutable* iterator over the resources in the bag.
+    pub fn iter(&self) -> Iter<'_, GenericResource> {
+        self.resources.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ResourceBag {
+    type Item = &'a GenericResource;
+    type IntoIter = Iter<'a, GenericResource>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl BagView for ResourceBag {
+    fn basic_counts(&self) -> HashMap<BasicResourceType, u32> {
+        let mut counts = HashMap::new();
+        for res in &self.resources {
+            if let ResourceType::Basic(basic) = res.get_type() {
+                *counts.entry(basic).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    fn complex_counts(&self) -> HashMap<ComplexResourceType, u32> {
+        let mut counts = HashMap::new();
+        for res in &self.resources {
+            if let ResourceType::Complex(complex) = res.get_type() {
+                *counts.entry(complex).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_bag_counts() {
+        let oxygen = ResourceType::Basic(BasicResourceType::Oxygen);
+        let water = ResourceType::Complex(ComplexResourceType::Water);
+
+        let mut bag = ResourceBag::new();
+        assert!(bag.is_empty());
+
+        bag.insert(GenericResource::fabricate(oxygen));
+        bag.insert(GenericResource::fabricate(oxygen));
+        bag.insert(GenericResource::fabricate(water));
+        assert_eq!(bag.len(), 3);
+
+        assert_eq!(bag.basic_counts()[&BasicResourceType::Oxygen], 2);
+        assert_eq!(bag.complex_counts()[&ComplexResourceType::Water], 1);
+        assert!(!bag.basic_counts().contains_key(&BasicResourceType::Carbon));
+
+        assert!(bag.take(water).is_some());
+        assert!(bag.take(water).is_none());
+        assert!(bag.complex_counts().is_empty());
+    }
+}
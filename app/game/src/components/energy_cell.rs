@@ -8,10 +8,45 @@ use crate::components::sunray::Sunray;
 use std::fmt::{Debug, Formatter};
 
 /// Represents an energy storage cell that can be charged by receiving a [Sunray].
+///
+/// Cells compare equal when they hold the same charge, which makes it easy to snapshot
+/// a set of cells (through [Clone]) and compare it later.
+/// With the `serde` feature enabled, the charge can be serialized to snapshot a whole planet state.
+/// Deserializing a cell holding more charges than its [capacity](EnergyCell::capacity) fails.
 #[allow(dead_code)]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawEnergyCell")
+)]
 pub struct EnergyCell {
-    /// Indicates whether the cell currently holds energy.
-    charge: bool,
+    /// The number of charges the cell currently holds, up to its capacity.
+    charge: u32,
+}
+
+// the unchecked shape of a serialized cell, validated by `TryFrom` before becoming an `EnergyCell`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawEnergyCell {
+    charge: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawEnergyCell> for EnergyCell {
+    type Error = String;
+
+    fn try_from(raw: RawEnergyCell) -> Result<Self, Self::Error> {
+        if raw.charge > Self::CAPACITY {
+            Err(format!(
+                "EnergyCell charge {} exceeds its capacity of {}",
+                raw.charge,
+                Self::CAPACITY
+            ))
+        } else {
+            Ok(Self { charge: raw.charge })
+        }
+    }
 }
 
 impl Default for EnergyCell {
@@ -29,38 +64,72 @@ impl Debug for EnergyCell {
 
 #[allow(dead_code)]
 impl EnergyCell {
+    // the number of charges a cell can hold
+    const CAPACITY: u32 = 1;
+
     /// Constructs a new `EnergyCell` that starts uncharged.
     #[must_use]
     pub fn new() -> Self {
-        Self { charge: false }
+        Self { charge: 0 }
+    }
+
+    /// Charges the cell using a [Sunray], adding the energy it carries (see [`Sunray::energy`]).
+    ///
+    /// The level of the cell is capped at its [capacity](EnergyCell::capacity): the energy
+    /// exceeding it is wasted. In particular, if the cell is already full, the sunray has no
+    /// additional effect.
+    ///
+    /// # Parameters
+    ///
+    /// - `sunray`: The sunray that charges the cell.
+    // the sunray is consumed on purpose, even when its energy is wasted
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn charge(&mut self, sunray: Sunray) {
+        // If already full, nothing happens and the Sunray is wasted.
+        self.charge = self
+            .charge
+            .saturating_add(sunray.energy())
+            .min(Self::CAPACITY);
     }
 
-    /// Charges the cell using a [Sunray].
+    /// Charges the cell using a [Sunray], handing the sunray back if it could not be absorbed.
     ///
-    /// If the cell is already charged, the sunray has no additional effect.
+    /// Unlike [`EnergyCell::charge`], a sunray received by an already full cell is not
+    /// wasted, so the caller can use it on another cell or account for it. A sunray absorbed
+    /// by a cell that isn't full is still capped at the capacity, like in [`EnergyCell::charge`].
     ///
     /// # Parameters
     ///
-    /// - `_sunray`: The sunray that charges the cell.
-    pub fn charge(&mut self, _sunray: Sunray) {
-        if !self.charge {
-            self.charge = true;
+    /// - `sunray`: The sunray that charges the cell.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the cell was not full and absorbed the sunray.
+    ///
+    /// # Errors
+    ///
+    /// - `Err(Sunray)` with the unabsorbed sunray if the cell was already full.
+    pub fn charge_checked(&mut self, sunray: Sunray) -> Result<(), Sunray> {
+        if self.charge >= Self::CAPACITY {
+            Err(sunray)
+        } else {
+            self.charge(sunray);
+            Ok(())
         }
-        // If already charged, nothing happens and the Sunray is wasted.
     }
 
-    /// Attempts to discharge the cell.
+    /// Attempts to discharge the cell, consuming a single charge.
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if the cell was charged and is now discharged.
+    /// - `Ok(())` if the cell was charged and a charge was consumed.
     ///
     /// # Errors
     ///
     /// - `Err(String)` if the cell was not charged.
     pub fn discharge(&mut self) -> Result<(), String> {
-        if self.charge {
-            self.charge = false;
+        if self.charge > 0 {
+            self.charge -= 1;
             Ok(())
         } else {
             Err("EnergyCell not charged!".to_string())
@@ -70,8 +139,23 @@ impl EnergyCell {
     /// Returns `true` if the cell currently holds a charge, false otherwise
     #[must_use]
     pub fn is_charged(&self) -> bool {
+        self.charge > 0
+    }
+
+    /// Returns the number of charges the cell currently holds, between `0` and
+    /// [`EnergyCell::capacity`].
+    #[must_use]
+    pub fn level(&self) -> u32 {
         self.charge
     }
+
+    /// Returns the maximum number of charges the cell can hold.
+    ///
+    /// A cell holds a single charge, so this is always `1` for now.
+    #[must_use]
+    pub fn capacity(&self) -> u32 {
+        Self::CAPACITY
+    }
 }
 
 #[cfg(test)]
@@ -132,4 +216,112 @@ mod tests {
         );
         assert_eq!(result.unwrap_err(), "EnergyCell not charged!");
     }
+
+    /// Checks that `charge_checked()` absorbs a sunray only when the cell is empty.
+    #[test]
+    fn charge_checked_returns_unabsorbed_sunray() {
+        let mut cell = EnergyCell::new();
+
+        assert!(
+            cell.charge_checked(Sunray::new()).is_ok(),
+            "An empty cell should absorb the sunray"
+        );
+        assert!(cell.is_charged());
+
+        assert!(
+            cell.charge_checked(Sunray::new()).is_err(),
+            "A charged cell should hand the sunray back"
+        );
+        assert!(cell.is_charged());
+    }
+
+    /// Checks that cells are compared (and cloned) by their charge.
+    #[test]
+    fn clone_and_eq_follow_charge() {
+        let mut cell = EnergyCell::new();
+        let snapshot = cell.clone();
+        assert_eq!(cell, snapshot);
+
+        cell.charge(Sunray::new());
+        assert_ne!(cell, snapshot, "Charging should change the comparison");
+        assert_eq!(cell.clone(), cell);
+    }
+
+    /// Checks that cells can be serialized with the `serde` feature.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn implements_serde() {
+        fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+        assert_serde::<EnergyCell>();
+    }
+
+    /// Checks that empty and charged cells survive a serialization round trip.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let empty = EnergyCell::new();
+        let mut charged = EnergyCell::new();
+        charged.charge(Sunray::new());
+
+        for cell in [empty, charged] {
+            let json = serde_json::to_string(&cell).unwrap();
+            let back: EnergyCell = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, cell);
+        }
+    }
+
+    /// Checks that a cell charged above its capacity is rejected when deserialized.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_charge_above_capacity() {
+        let result = serde_json::from_str::<EnergyCell>(r#"{"charge":7}"#);
+        assert!(
+            result.is_err(),
+            "A charge above capacity should be rejected"
+        );
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("exceeds its capacity")
+        );
+    }
+
+    /// Checks that the energy of a sunray is capped at the capacity of the cell.
+    #[test]
+    fn charge_is_capped_at_capacity() {
+        let mut cell = EnergyCell::new();
+        cell.charge(Sunray::with_energy(0));
+        assert!(
+            !cell.is_charged(),
+            "A sunray without energy should not charge"
+        );
+
+        cell.charge(Sunray::with_energy(5));
+        assert_eq!(
+            cell.level(),
+            cell.capacity(),
+            "The excess energy should be wasted"
+        );
+
+        let sunray = cell.charge_checked(Sunray::with_energy(3)).unwrap_err();
+        assert_eq!(
+            sunray.energy(),
+            3,
+            "A full cell should hand the sunray back untouched"
+        );
+
+        cell.discharge().unwrap();
+        assert_eq!(cell.level(), cell.capacity() - 1);
+    }
+
+    /// Checks that the level goes from 0 to the capacity when the cell is charged.
+    #[test]
+    fn level_and_capacity() {
+        let mut cell = EnergyCell::new();
+        assert_eq!((cell.level(), cell.capacity()), (0, 1));
+
+        cell.charge(Sunray::new());
+        assert_eq!(cell.level(), cell.capacity());
+    }
 }
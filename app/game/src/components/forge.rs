@@ -9,6 +9,10 @@
 
 use crate::components::asteroid::Asteroid;
 use crate::components::sunray::Sunray;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+use std::time::Instant;
 
 /// Internal module containing global state used by the [Forge].
 ///
@@ -37,6 +41,65 @@ pub(crate) mod internal {
 pub struct Forge {
     /// Hidden field to prevent external construction.
     _private: (),
+    /// Optional limit on the generation rate, `None` if unlimited.
+    limiter: Option<Mutex<TokenBucket>>,
+}
+
+/// Errors returned by the [Forge] generation methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForgeError {
+    /// The rate limit set with [`Forge::with_rate_limit`] has been exceeded.
+    RateLimited,
+}
+
+impl Display for ForgeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ForgeError::RateLimited => write!(f, "Forge rate limit exceeded"),
+        }
+    }
+}
+
+impl Error for ForgeError {}
+
+/// Token bucket limiting the [Forge] generation rate.
+///
+/// Holds up to `capacity` tokens, starts full and refills continuously
+/// at `capacity` tokens per second; each generation takes one token.
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket, refilling from `now`.
+    fn new(max_per_sec: u32, now: Instant) -> Self {
+        let capacity = f64::from(max_per_sec);
+        TokenBucket {
+            capacity,
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    /// Refills the bucket for the time elapsed between the last call and `now`,
+    /// then takes `n` tokens if there are enough of them (and none otherwise).
+    ///
+    /// The current time is passed in, so that the refill can be tested without waiting.
+    fn try_take(&mut self, n: usize, now: Instant) -> bool {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.capacity).min(self.capacity);
+        self.last_refill = now;
+
+        let needed = u32::try_from(n).map_or(f64::INFINITY, f64::from);
+        if self.tokens >= needed {
+            self.tokens -= needed;
+            true
+        } else {
+            false
+        }
+    }
 }
 
 impl Forge {
@@ -57,7 +120,43 @@ impl Forge {
             Err("Another generator has already been created".into())
         } else {
             *check = true;
-            Ok(Forge { _private: () })
+            Ok(Forge {
+                _private: (),
+                limiter: None,
+            })
+        }
+    }
+
+    /// Limits the Forge to generate at most `max_per_sec` objects per second,
+    /// [`Asteroid`]s and [`Sunray`]s combined. By default the Forge is unlimited.
+    ///
+    /// The limit is enforced with a token bucket holding up to `max_per_sec` tokens:
+    /// it starts full, so a burst of `max_per_sec` objects can be generated at once,
+    /// and it refills continuously at `max_per_sec` tokens per second
+    /// (e.g. one token every 100ms with a limit of 10). Each generation takes a token,
+    /// and fails with [`ForgeError::RateLimited`] when the bucket is empty.
+    ///
+    /// A limit of `0` prevents any generation.
+    #[must_use]
+    pub fn with_rate_limit(mut self, max_per_sec: u32) -> Self {
+        self.limiter = Some(Mutex::new(TokenBucket::new(max_per_sec, Instant::now())));
+        self
+    }
+
+    // takes `n` tokens from the rate limiter, if there are enough of them
+    fn acquire(&self, n: usize) -> Result<(), ForgeError> {
+        let Some(limiter) = &self.limiter else {
+            return Ok(());
+        };
+
+        // a poisoned bucket is still a valid bucket
+        let mut bucket = limiter
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if bucket.try_take(n, Instant::now()) {
+            Ok(())
+        } else {
+            Err(ForgeError::RateLimited)
         }
     }
 
@@ -65,18 +164,57 @@ impl Forge {
     ///
     /// # Returns
     /// A freshly constructed `Asteroid` instance.
-    #[must_use]
-    pub fn generate_asteroid(&self) -> Asteroid {
-        Asteroid::new()
+    ///
+    /// # Errors
+    /// Returns [`ForgeError::RateLimited`] if the rate limit (see [`Forge::with_rate_limit`])
+    /// has been exceeded.
+    pub fn generate_asteroid(&self) -> Result<Asteroid, ForgeError> {
+        self.acquire(1).map(|()| Asteroid::new())
     }
 
     /// Creates a new [`Sunray`].
     ///
     /// # Returns
     /// A freshly constructed `Sunray` instance.
-    #[must_use]
-    pub fn generate_sunray(&self) -> Sunray {
-        Sunray::new()
+    ///
+    /// # Errors
+    /// Returns [`ForgeError::RateLimited`] if the rate limit (see [`Forge::with_rate_limit`])
+    /// has been exceeded.
+    pub fn generate_sunray(&self) -> Result<Sunray, ForgeError> {
+        self.acquire(1).map(|()| Sunray::new())
+    }
+
+    /// Creates a new [`Sunray`] carrying `amount` charges, to model a stronger (or weaker) light.
+    ///
+    /// An [`EnergyCell`](crate::components::energy_cell::EnergyCell) absorbs the charges up
+    /// to its capacity: the excess is wasted. A sunray with no energy charges nothing.
+    ///
+    /// # Errors
+    /// Returns [`ForgeError::RateLimited`] if the rate limit (see [`Forge::with_rate_limit`])
+    /// has been exceeded.
+    pub fn generate_sunray_with_energy(&self, amount: u32) -> Result<Sunray, ForgeError> {
+        self.acquire(1).map(|()| Sunray::with_energy(amount))
+    }
+
+    /// Creates `n` new [`Asteroid`]s at once.
+    ///
+    /// # Errors
+    /// Returns [`ForgeError::RateLimited`] if generating `n` objects would exceed the rate limit
+    /// (see [`Forge::with_rate_limit`]): in that case no asteroid is generated.
+    pub fn generate_asteroids(&self, n: usize) -> Result<Vec<Asteroid>, ForgeError> {
+        self.acquire(n)
+            .map(|()| (0..n).map(|_| Asteroid::new()).collect())
+    }
+
+    /// Creates `n` new [`Sunray`]s at once, e.g. to send them in an
+    /// [`OrchestratorToPlanet::SunrayBurst`](crate::protocols::orchestrator_planet::OrchestratorToPlanet::SunrayBurst).
+    ///
+    /// # Errors
+    /// Returns [`ForgeError::RateLimited`] if generating `n` objects would exceed the rate limit
+    /// (see [`Forge::with_rate_limit`]): in that case no sunray is generated.
+    pub fn generate_sunrays(&self, n: usize) -> Result<Vec<Sunray>, ForgeError> {
+        self.acquire(n)
+            .map(|()| (0..n).map(|_| Sunray::new()).collect())
     }
 }
 
@@ -88,6 +226,7 @@ mod tests {
 
     use super::internal::ALREADY_CREATED;
     use super::*;
+    use std::time::Duration;
 
     /// Resets the global singleton state.
     ///
@@ -117,4 +256,93 @@ mod tests {
         let g1 = Forge::new();
         assert!(g1.is_err());
     }
+
+    /// Checks that an unlimited Forge always generates.
+    #[test]
+    fn unlimited_by_default() {
+        let forge = Forge {
+            _private: (),
+            limiter: None,
+        };
+        for _ in 0..100 {
+            assert!(forge.generate_sunray().is_ok());
+        }
+    }
+
+    /// Checks that the bucket allows a burst of `max_per_sec`, then refuses until it refills.
+    #[test]
+    fn rate_limit_caps_generation() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(3, start);
+
+        for _ in 0..3 {
+            assert!(bucket.try_take(1, start));
+        }
+        assert!(!bucket.try_take(1, start));
+
+        // one token every 333ms
+        assert!(!bucket.try_take(1, start + Duration::from_millis(300)));
+        assert!(bucket.try_take(1, start + Duration::from_millis(400)));
+        assert!(!bucket.try_take(1, start + Duration::from_millis(400)));
+
+        // and never more than `max_per_sec` of them
+        let later = start + Duration::from_secs(10);
+        for _ in 0..3 {
+            assert!(bucket.try_take(1, later));
+        }
+        assert!(!bucket.try_take(1, later));
+    }
+
+    /// Checks that taking several tokens is all-or-nothing.
+    #[test]
+    fn batch_generation() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(5, start);
+
+        assert!(bucket.try_take(3, start));
+        assert!(!bucket.try_take(3, start));
+        assert!(bucket.try_take(2, start));
+        assert!(bucket.try_take(0, start));
+        assert!(!bucket.try_take(1, start));
+    }
+
+    /// Checks that the generation methods go through the rate limit.
+    #[test]
+    fn rate_limited_forge() {
+        let forge = Forge {
+            _private: (),
+            limiter: None,
+        }
+        .with_rate_limit(0);
+
+        assert_eq!(forge.generate_sunray().err(), Some(ForgeError::RateLimited));
+        assert_eq!(
+            forge.generate_asteroid().err(),
+            Some(ForgeError::RateLimited)
+        );
+        assert_eq!(
+            forge.generate_sunrays(2).err(),
+            Some(ForgeError::RateLimited)
+        );
+        assert!(
+            forge
+                .generate_asteroids(0)
+                .is_ok_and(|rocks| rocks.is_empty())
+        );
+    }
+
+    /// Checks that the energy of the generated sunrays can be chosen.
+    #[test]
+    fn sunray_energy() {
+        let forge = Forge {
+            _private: (),
+            limiter: None,
+        };
+
+        assert_eq!(forge.generate_sunray().map(|ray| ray.energy()), Ok(1));
+        assert_eq!(
+            forge.generate_sunray_with_energy(4).map(|ray| ray.energy()),
+            Ok(4)
+        );
+    }
 }
@@ -97,15 +97,28 @@
 //! ```
 
 use crate::components::energy_cell::EnergyCell;
-use crate::components::resource::{BasicResourceType, Combinator, ComplexResourceType, Generator};
+use crate::components::resource::{
+    BasicResourceType, Combinator, ComplexResourceType, GenerateError, Generator, GenericResource,
+    ResourceType, reachable_from,
+};
 use crate::components::rocket::Rocket;
 use crate::components::sunray::Sunray;
-use crate::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
-use crate::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
+use crate::logging::{ActorType, Channel, LogEvent, Participant, Payload};
+use crate::protocols::orchestrator_planet::{
+    AsteroidOutcome, DestroyCause, OrchestratorToPlanet, OrchestratorToPlanetKind,
+    PlanetToOrchestrator, PlanetToOrchestratorKind,
+};
+use crate::protocols::planet_explorer::{ExplorerToPlanet, ExplorerToPlanetKind, PlanetToExplorer};
 use crate::utils::ID;
-use crossbeam_channel::{Receiver, Sender, select_biased};
-use std::collections::HashMap;
+use crossbeam_channel::{Receiver, Select, SendError, SendTimeoutError, Sender, TryRecvError};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use std::slice::{Iter, IterMut};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 /// The trait that defines the **behavior** of a planet, meaning how it reacts
 /// to messages coming from the orchestrator and explorers. This is done through trait methods
@@ -130,6 +143,26 @@ pub trait PlanetAI: Send {
         sunray: Sunray,
     );
 
+    /// This handler will be invoked when a [`OrchestratorToPlanet::SunrayBurst`]
+    /// message is received, with all the sunrays of the burst.
+    ///
+    /// By default, the sunrays charge the empty cells in order (see [`PlanetState::charge_cells`]).
+    ///
+    /// # Returns
+    /// The number of sunrays that charged a cell, the others are reported as wasted
+    /// in [`PlanetToOrchestrator::SunrayBurstAck`].
+    #[allow(unused_variables)]
+    fn handle_sunray_burst(
+        &mut self,
+        state: &mut PlanetState,
+        generator: &Generator,
+        combinator: &Combinator,
+        sunrays: Vec<Sunray>,
+    ) -> usize {
+        let n = sunrays.len();
+        n - state.charge_cells(sunrays).len()
+    }
+
     /// This handler will be invoked when a [`OrchestratorToPlanet::Asteroid`]
     /// message is received. It's important to handle *Asteroid* messages
     /// correctly, as this will the determine the planet survival.
@@ -161,6 +194,11 @@ pub trait PlanetAI: Send {
     /// end of the [`ExplorerToPlanet`] channel). The id of the sender explorer
     /// is part of the `msg` struct.
     ///
+    /// This handler is **only** invoked for explorers that are present on the planet
+    /// (see [`PlanetState::present_explorers`]): messages from explorers that never
+    /// arrived, that already left or that disconnected (a previous response could not be
+    /// delivered to them) are dropped without reaching the AI.
+    ///
     /// # Returns
     /// This method can return an optional response to the message, which will
     /// be delivered to the explorer that sent the message.
@@ -172,6 +210,24 @@ pub trait PlanetAI: Send {
         msg: ExplorerToPlanet,
     ) -> Option<PlanetToExplorer>;
 
+    /// This method will be invoked when an explorer (identified by the `explorer_id`
+    /// parameter) asks to land on the planet, before it is accepted.
+    ///
+    /// # Returns
+    /// `true` to accept the explorer, `false` to answer the orchestrator with an error and keep
+    /// the explorer out. By default, explorers are accepted as long as the planet has room for them
+    /// (see [`PlanetState::has_room_for_explorer`]).
+    #[allow(unused_variables)]
+    fn accept_explorer(
+        &mut self,
+        state: &PlanetState,
+        generator: &Generator,
+        combinator: &Combinator,
+        explorer_id: ID,
+    ) -> bool {
+        state.has_room_for_explorer()
+    }
+
     /// This method will be invoked when an explorer (identified by the `explorer_id`
     /// parameter) lands on the planet.
     #[allow(unused_variables)]
@@ -186,6 +242,10 @@ pub trait PlanetAI: Send {
 
     /// This method will be invoked when an explorer (identified by the `explorer_id`
     /// parameter) leaves the planet.
+    ///
+    /// Messages from this explorer still queued on the planet channel are not delivered to
+    /// [`PlanetAI::handle_explorer_msg`] anymore: they are dropped and reported with a
+    /// [`Channel::Warning`] log event.
     #[allow(unused_variables)]
     fn on_explorer_departure(
         &mut self,
@@ -207,8 +267,163 @@ pub trait PlanetAI: Send {
     /// is received, but **only if** the planet is currently in a *running* state.
     ///
     /// Stop messages received when planet is already stopped are **ignored**.
+    ///
+    /// # Returns
+    /// `true` if the AI is ready to stop (default), `false` to *veto* the stop, e.g. in the middle of
+    /// a multi-step operation: in that case the planet keeps running and answers with
+    /// [`PlanetToOrchestrator::StopDeferred`], so the orchestrator can retry later.
+    #[allow(unused_variables)]
+    fn on_stop(
+        &mut self,
+        state: &PlanetState,
+        generator: &Generator,
+        combinator: &Combinator,
+    ) -> bool {
+        true
+    }
+
+    /// This method will be invoked when a [`OrchestratorToPlanet::ResetPlanetAI`]
+    /// is received while the planet is *running*.
+    ///
+    /// Implementors should wipe any AI-internal state here: the energy cells, the rocket
+    /// and the recipes of the planet are left untouched.
+    #[allow(unused_variables)]
+    fn on_reset(&mut self, state: &PlanetState, generator: &Generator, combinator: &Combinator) {}
+
+    /// This method will be invoked when a message of kind `request_kind` is received from the
+    /// orchestrator while the planet is *running*, before the message is handled.
+    ///
+    /// It isn't invoked for [`OrchestratorToPlanet::Ping`], [`OrchestratorToPlanet::StartPlanetAI`],
+    /// [`OrchestratorToPlanet::KillPlanet`] and [`OrchestratorToPlanet::Asteroid`], which can't be
+    /// declined.
+    ///
+    /// # Returns
+    /// `None` to handle the message as usual (default), or the reason to decline it, e.g. an
+    /// [`OrchestratorToPlanet::InternalStateRequest`] received in the middle of a multi-step
+    /// operation: in that case the message (and anything it carries) is dropped and the planet
+    /// answers with [`PlanetToOrchestrator::Nack`].
     #[allow(unused_variables)]
-    fn on_stop(&mut self, state: &PlanetState, generator: &Generator, combinator: &Combinator) {}
+    fn decline_request(
+        &mut self,
+        state: &PlanetState,
+        generator: &Generator,
+        combinator: &Combinator,
+        request_kind: OrchestratorToPlanetKind,
+    ) -> Option<String> {
+        None
+    }
+}
+
+/// A ready-made [`PlanetAI`] that just keeps the planet alive and serves the explorers,
+/// e.g. for integration tests or as a reference implementation.
+///
+/// Its behavior is:
+/// - **Sunray**: charges the first discharged energy cell (the sunray is wasted if all the
+///   cells are already charged).
+/// - **Asteroid**: returns the stored rocket, if any; otherwise builds one with the first
+///   charged cell and returns it. Without rockets nor charged cells the planet is destroyed.
+/// - **Internal state**: returns [`PlanetState::to_dummy`].
+/// - **Explorer messages**:
+///   - supported resources and combinations are answered with
+///     [`PlanetToExplorer::supported_resources_from`] and
+///     [`PlanetToExplorer::supported_combinations_from`];
+///   - generation and combination requests use the first charged cell, failing if there is none;
+///   - the available cells are the charged ones ([`PlanetState::charged_cells_count`]);
+///   - every deposit is accepted;
+///   - stored resources are answered with [`PlanetToExplorer::stored_resources_from`].
+///
+/// It keeps no internal state, so starts, stops and resets have no effect on it.
+#[derive(Debug, Default)]
+pub struct DefaultPlanetAI {
+    _private: (),
+}
+
+impl DefaultPlanetAI {
+    /// Creates a new `DefaultPlanetAI`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PlanetAI for DefaultPlanetAI {
+    fn handle_sunray(
+        &mut self,
+        state: &mut PlanetState,
+        _generator: &Generator,
+        _combinator: &Combinator,
+        sunray: Sunray,
+    ) {
+        let _ = state.charge_cell(sunray);
+    }
+
+    fn handle_asteroid(
+        &mut self,
+        state: &mut PlanetState,
+        _generator: &Generator,
+        _combinator: &Combinator,
+    ) -> Option<Rocket> {
+        if !state.has_rocket() {
+            let _ = state.build_rocket_from_any();
+        }
+        state.take_rocket()
+    }
+
+    fn handle_internal_state_req(
+        &mut self,
+        state: &mut PlanetState,
+        _generator: &Generator,
+        _combinator: &Combinator,
+    ) -> DummyPlanetState {
+        state.to_dummy()
+    }
+
+    fn handle_explorer_msg(
+        &mut self,
+        state: &mut PlanetState,
+        generator: &Generator,
+        combinator: &Combinator,
+        msg: ExplorerToPlanet,
+    ) -> Option<PlanetToExplorer> {
+        let response = match msg {
+            ExplorerToPlanet::SupportedResourceRequest { .. } => {
+                PlanetToExplorer::supported_resources_from(generator)
+            }
+            ExplorerToPlanet::SupportedCombinationRequest { .. } => {
+                PlanetToExplorer::supported_combinations_from(combinator)
+            }
+            ExplorerToPlanet::GenerateResourceRequest { resource, .. } => {
+                PlanetToExplorer::GenerateResourceResponse {
+                    resource: state
+                        .full_cell()
+                        .and_then(|(cell, _)| generator.try_make(resource, cell).ok()),
+                }
+            }
+            ExplorerToPlanet::CombineResourceRequest { msg, .. } => {
+                // an uncharged cell makes the combination fail, giving the resources back
+                let i = state.full_cell().map_or(0, |(_, i)| i);
+                PlanetToExplorer::CombineResourceResponse {
+                    complex_response: combinator.try_make(msg, state.cell_mut(i)),
+                }
+            }
+            ExplorerToPlanet::AvailableEnergyCellRequest { .. } => {
+                PlanetToExplorer::AvailableEnergyCellResponse {
+                    available_cells: ID::try_from(state.charged_cells_count()).unwrap_or(ID::MAX),
+                }
+            }
+            ExplorerToPlanet::DepositResourceRequest { resource, .. } => {
+                state.store_resource(resource);
+                PlanetToExplorer::DepositResourceResponse { res: Ok(()) }
+            }
+            ExplorerToPlanet::StoredResourcesRequest { .. } => {
+                PlanetToExplorer::stored_resources_from(state)
+            }
+            ExplorerToPlanet::AffordableCombinationsRequest { .. } => {
+                PlanetToExplorer::affordable_combinations_from(state, combinator)
+            }
+        };
+        Some(response)
+    }
 }
 
 /// Contains planet rules constraints (see [`PlanetType`]).
@@ -222,7 +437,7 @@ pub struct PlanetConstraints {
 /// Planet types definitions, intended to be passed
 /// to the planet constructor. Identifies the planet rules constraints,
 /// with each type having its own rules.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PlanetType {
     A,
     B,
@@ -265,16 +480,146 @@ impl PlanetType {
             },
         }
     }
+
+    /// Returns the `PlanetType` denoted by the letter `c` (`'A'` to `'D'`, case-insensitive),
+    /// or `None` for any other character.
+    #[must_use]
+    pub fn from_char(c: char) -> Option<PlanetType> {
+        match c.to_ascii_uppercase() {
+            'A' => Some(PlanetType::A),
+            'B' => Some(PlanetType::B),
+            'C' => Some(PlanetType::C),
+            'D' => Some(PlanetType::D),
+            _ => None,
+        }
+    }
+
+    /// Returns the (uppercase) letter denoting the planet type,
+    /// the inverse of [`PlanetType::from_char`].
+    #[must_use]
+    pub fn as_char(&self) -> char {
+        match self {
+            PlanetType::A => 'A',
+            PlanetType::B => 'B',
+            PlanetType::C => 'C',
+            PlanetType::D => 'D',
+        }
+    }
+
+    /// Returns the maximum number of generation rules a planet of this type can have,
+    /// or `None` if they are unbounded.
+    #[must_use]
+    pub fn max_gen_rules(&self) -> Option<usize> {
+        if self.constraints().unbounded_gen_rules {
+            None
+        } else {
+            Some(1)
+        }
+    }
+
+    /// Returns the maximum number of combination rules a planet of this type can have.
+    #[must_use]
+    pub fn max_comb_rules(&self) -> usize {
+        self.constraints().n_comb_rules
+    }
+
+    /// Checks a set of generation and combination rules against the constraints
+    /// of this planet type, collecting **all** the violations instead of stopping at the first one.
+    ///
+    /// This is the same validation performed by [`Planet::new`].
+    ///
+    /// # Errors
+    /// Returns a [Vec] with a description of each violation found:
+    /// empty generation rules, too many generation or combination rules, and duplicate rules.
+    pub fn validate_rules(
+        &self,
+        gen_rules: &[BasicResourceType],
+        comb_rules: &[ComplexResourceType],
+    ) -> Result<(), Vec<String>> {
+        let n_comb_rules = self.max_comb_rules();
+        let mut errors = Vec::new();
+
+        if gen_rules.is_empty() {
+            errors.push("gen_rules is empty".to_string());
+        } else if let Some(n_gen_rules) = self.max_gen_rules()
+            && gen_rules.len() > n_gen_rules
+        {
+            errors.push(format!(
+                "Too many generation rules (Planet type {self:?} is limited to {n_gen_rules})"
+            ));
+        }
+        if comb_rules.len() > n_comb_rules {
+            errors.push(format!(
+                "Too many combination rules (Planet type {self:?} is limited to {n_comb_rules})"
+            ));
+        }
+
+        let mut seen_gen = HashSet::new();
+        for r in gen_rules {
+            if !seen_gen.insert(r) {
+                errors.push(format!("Duplicate generation rule {r:?}"));
+            }
+        }
+        let mut seen_comb = HashSet::new();
+        for r in comb_rules {
+            if !seen_comb.insert(r) {
+                errors.push(format!("Duplicate combination rule {r:?}"));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl FromStr for PlanetType {
+    type Err = ParsePlanetTypeError;
+
+    /// Parses a planet type from its letter (see [`PlanetType::from_char`]).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => PlanetType::from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| ParsePlanetTypeError::UnknownType(s.to_string()))
+    }
+}
+
+/// Error returned when parsing a [`PlanetType`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePlanetTypeError {
+    /// The given string is not the letter of a planet type (`A` to `D`).
+    UnknownType(String),
+}
+
+impl Display for ParsePlanetTypeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParsePlanetTypeError::UnknownType(s) => write!(f, "Unknown planet type: {s:?}"),
+        }
+    }
 }
 
+impl Error for ParsePlanetTypeError {}
+
 /// This struct is a representation of the internal state
 /// of the planet. Through its public methods, it gives access to the
-/// energy cells and rocket construction of the planet.
+/// energy cells, rocket construction and resources storage of the planet.
 pub struct PlanetState {
     id: ID,
+    name: Option<String>,
     energy_cells: Vec<EnergyCell>,
     rocket: Option<Rocket>,
     can_have_rocket: bool,
+    resources: Vec<GenericResource>,
+    present_explorers: HashSet<ID>,
+    max_explorers: Option<usize>,
+    // when the planet last received a message, monitoring ones (`Ping`, `InternalStateRequest`) aside
+    last_activity: Instant,
 }
 
 impl PlanetState {
@@ -284,6 +629,13 @@ impl PlanetState {
         self.id
     }
 
+    /// Returns the human-readable name of the planet, if it has one
+    /// (see [`Planet::with_name`]).
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     /// Indexed getter accessor for the [`EnergyCell`] vec.
     ///
     /// # Returns
@@ -322,6 +674,15 @@ impl PlanetState {
         self.energy_cells.iter()
     }
 
+    /// Returns an iterator over the index and charge state of each energy cell owned by the planet,
+    /// i.e. `(i, cell(i).is_charged())`.
+    pub fn cells_enumerate(&self) -> impl Iterator<Item = (usize, bool)> + '_ {
+        self.energy_cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (i, cell.is_charged()))
+    }
+
     /// Returns a *mutable* iterator over the energy cells owned by the planet.
     pub fn cells_iter_mut(&mut self) -> IterMut<'_, EnergyCell> {
         self.energy_cells.iter_mut()
@@ -339,6 +700,44 @@ impl PlanetState {
         }
     }
 
+    /// Charges the empty (discharged) cells in order, one per sunray.
+    /// Returns the sunrays left over once all the cells are charged.
+    pub fn charge_cells(&mut self, sunrays: impl IntoIterator<Item = Sunray>) -> Vec<Sunray> {
+        sunrays
+            .into_iter()
+            .filter_map(|sunray| self.charge_cell(sunray))
+            .collect()
+    }
+
+    /// Discharges the *i-th* [`EnergyCell`], e.g. to power some custom action of the AI.
+    ///
+    /// Unlike [`PlanetState::cell_mut`], this method never panics.
+    ///
+    /// # Errors
+    /// Returns an error if the index `i` is out of bounds or if the cell is not charged.
+    pub fn discharge_cell(&mut self, i: usize) -> Result<(), String> {
+        match self.energy_cells.get_mut(i) {
+            None => Err(format!(
+                "Energy cell index {i} out of bounds (the planet has {} cells)",
+                self.energy_cells.len()
+            )),
+            Some(cell) => cell.discharge(),
+        }
+    }
+
+    /// Charges the *i-th* [`EnergyCell`] with a [Sunray].
+    ///
+    /// Unlike [`PlanetState::cell_mut`], this method never panics.
+    ///
+    /// # Errors
+    /// Returns the [Sunray] back if the index `i` is out of bounds or if the cell is already charged.
+    pub fn charge_cell_at(&mut self, i: usize, sunray: Sunray) -> Result<(), Sunray> {
+        match self.energy_cells.get_mut(i) {
+            None => Err(sunray),
+            Some(cell) => cell.charge_checked(sunray),
+        }
+    }
+
     /// Returns a tuple containing a *mutable* borrow of the first empty (discharged) cell
     /// and its index, or `None` if there isn't any.
     pub fn empty_cell(&mut self) -> Option<(&mut EnergyCell, usize)> {
@@ -399,34 +798,333 @@ impl PlanetState {
         }
     }
 
+    /// Constructs a rocket using the first charged [`EnergyCell`] of the planet (see
+    /// [`PlanetState::full_cell`]) and stores it inside the planet, like [`PlanetState::build_rocket`].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The planet type prohibits the storing of rockets.
+    /// - The planet already has a rocket built.
+    /// - No energy cell is charged.
+    pub fn build_rocket_from_any(&mut self) -> Result<(), String> {
+        if !self.can_have_rocket {
+            return Err("This planet type can't have rockets.".to_string());
+        }
+        let (_, i) = self
+            .full_cell()
+            .ok_or_else(|| "No EnergyCell is charged.".to_string())?;
+        self.build_rocket(i)
+    }
+
+    /// Stores a resource in the planet storage, e.g. one deposited by an explorer
+    /// with [`ExplorerToPlanet::DepositResourceRequest`] or an intermediate product
+    /// the AI wants to keep across several messages.
+    pub fn store_resource(&mut self, resource: GenericResource) {
+        self.resources.push(resource);
+    }
+
+    /// Generates a basic resource of type `t` with the first charged cell and stores it
+    /// in the planet storage (see [`PlanetState::store_resource`]).
+    ///
+    /// # Errors
+    /// - [`GenerateError::MissingRecipe`] if `generator` has no recipe for `t`.
+    /// - [`GenerateError::CellNotCharged`] if there is no charged cell.
+    pub fn generate_into_storage(
+        &mut self,
+        generator: &Generator,
+        t: BasicResourceType,
+    ) -> Result<(), GenerateError> {
+        if !generator.contains(t) {
+            return Err(GenerateError::MissingRecipe(t));
+        }
+        let (cell, _) = self.full_cell().ok_or(GenerateError::CellNotCharged)?;
+        let resource = generator.try_make(t, cell)?;
+        self.store_resource(GenericResource::BasicResources(resource));
+        Ok(())
+    }
+
+    /// Returns an *immutable* iterator over the resources stored in the planet.
+    pub fn resources_iter(&self) -> Iter<'_, GenericResource> {
+        self.resources.iter()
+    }
+
+    /// Takes the first stored resource of the given type out of the planet storage,
+    /// or returns `None` if there isn't any.
+    pub fn take_resource(&mut self, resource_type: ResourceType) -> Option<GenericResource> {
+        let idx = self
+            .resources
+            .iter()
+            .position(|res| res.get_type() == resource_type)?;
+        Some(self.resources.remove(idx))
+    }
+
+    /// Returns the number of charged energy cells of the planet.
+    #[must_use]
+    pub fn charged_cells_count(&self) -> usize {
+        self.energy_cells
+            .iter()
+            .filter(|cell| cell.is_charged())
+            .count()
+    }
+
+    /// Returns `true` if at least `n` energy cells of the planet are charged.
+    #[must_use]
+    pub fn has_charged_cells(&self, n: usize) -> bool {
+        self.charged_cells_count() >= n
+    }
+
+    /// Returns `true` if the planet has enough charged energy cells to make `target`
+    /// (see [`ComplexResourceType::cells_needed`]).
+    ///
+    /// This assumes all the inputs are generated from scratch on this planet: it doesn't
+    /// account for stored resources nor check that the planet has the needed recipes.
+    #[must_use]
+    pub fn can_afford(&self, target: ComplexResourceType) -> bool {
+        self.charged_cells_count() >= target.cells_needed()
+    }
+
+    /// Returns the ids of the explorers currently on the planet.
+    #[must_use]
+    pub fn present_explorers(&self) -> &HashSet<ID> {
+        &self.present_explorers
+    }
+
+    /// Returns `true` if the explorer with the given id is currently on the planet.
+    #[must_use]
+    pub fn is_explorer_present(&self, explorer_id: ID) -> bool {
+        self.present_explorers.contains(&explorer_id)
+    }
+
+    /// Returns the maximum number of explorers the planet accepts at once
+    /// (see [`Planet::with_max_explorers`]), or `None` if there is no limit.
+    #[must_use]
+    pub fn max_explorers(&self) -> Option<usize> {
+        self.max_explorers
+    }
+
+    /// Returns `true` if the planet has room for another explorer,
+    /// i.e. it has no explorer limit or it's not reached yet.
+    #[must_use]
+    pub fn has_room_for_explorer(&self) -> bool {
+        self.max_explorers
+            .is_none_or(|max| self.present_explorers.len() < max)
+    }
+
+    /// Returns the number of resources stored in the planet.
+    #[must_use]
+    pub fn stored_resources_count(&self) -> usize {
+        self.resources.len()
+    }
+
     /// Returns a *dummy* clone of this state.
     #[must_use]
     pub fn to_dummy(&self) -> DummyPlanetState {
         DummyPlanetState {
+            name: self.name.clone(),
             energy_cells: self
                 .energy_cells
                 .iter()
                 .map(super::energy_cell::EnergyCell::is_charged)
                 .collect(),
-            charged_cells_count: self
+            cell_levels: self
                 .energy_cells
                 .iter()
-                .filter(|cell| cell.is_charged())
-                .count(),
+                .map(|cell| (cell.level(), cell.capacity()))
+                .collect(),
+            charged_cells_count: self.charged_cells_count(),
             has_rocket: self.has_rocket(),
+            stored_resources: self.stored_resources_count(),
+            explorers_count: self.present_explorers.len(),
+            max_explorers: self.max_explorers,
+            seconds_since_last_activity: self.last_activity.elapsed().as_secs(),
         }
     }
 }
 
+/// Describes why a planet is currently in a *stopped* state.
+///
+/// Carried by [`PlanetToOrchestrator::Stopped`] and [`PlanetToExplorer::Stopped`],
+/// so that the receiver can tell a deliberately paused planet from one that has
+/// never been started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The planet AI was stopped by an [`OrchestratorToPlanet::StopPlanetAI`] message.
+    ManualStop,
+    /// The planet AI has not received its first [`OrchestratorToPlanet::StartPlanetAI`] message yet.
+    NeverStarted,
+}
+
 /// This is a dummy struct containing an overview of the internal state of a planet.
 /// Use [`PlanetState::to_dummy`] to construct one.
 ///
 /// Used in [`PlanetToOrchestrator::InternalStateResponse`].
+/// With the `serde` feature enabled, it can be serialized to be sent to another process (e.g. a GUI).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DummyPlanetState {
+    /// The name of the planet, if it has one (see [`Planet::with_name`]).
+    pub name: Option<String>,
     pub energy_cells: Vec<bool>,
+    /// The `(level, capacity)` of each energy cell (see [`EnergyCell::level`]),
+    /// to render partial charges: a cell is charged in `energy_cells` if its level is not `0`.
+    pub cell_levels: Vec<(u32, u32)>,
     pub charged_cells_count: usize,
     pub has_rocket: bool,
+    pub stored_resources: usize,
+    pub explorers_count: usize,
+    pub max_explorers: Option<usize>,
+    /// Seconds elapsed since the planet last received a message, when the snapshot was taken:
+    /// a planet that stays idle for long while being sent messages is probably hung or starved.
+    /// [`OrchestratorToPlanet::Ping`] and [`OrchestratorToPlanet::InternalStateRequest`] are
+    /// not counted, so that monitoring the planet doesn't reset it.
+    pub seconds_since_last_activity: u64,
+}
+
+impl DummyPlanetState {
+    /// Returns what changed going from the `self` snapshot to the (later) `other` one.
+    ///
+    /// Both snapshots are expected to come from the same planet: cells that only exist in one
+    /// of them are ignored.
+    #[must_use]
+    pub fn diff(&self, other: &DummyPlanetState) -> StateDiff {
+        let cells = self
+            .energy_cells
+            .iter()
+            .zip(&other.energy_cells)
+            .enumerate()
+            .filter_map(|(i, (&before, &after))| match (before, after) {
+                (false, true) => Some((i, CellTransition::Charged)),
+                (true, false) => Some((i, CellTransition::Discharged)),
+                _ => None,
+            })
+            .collect();
+
+        StateDiff {
+            cells,
+            rocket_delta: i8::from(other.has_rocket) - i8::from(self.has_rocket),
+        }
+    }
+}
+
+/// How an energy cell changed between two [`DummyPlanetState`] snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellTransition {
+    /// The cell was discharged and is now charged.
+    Charged,
+    /// The cell was charged and is now discharged.
+    Discharged,
+}
+
+/// The changes between two [`DummyPlanetState`] snapshots, see [`DummyPlanetState::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateDiff {
+    /// The cells that changed, by index, in increasing index order.
+    pub cells: Vec<(usize, CellTransition)>,
+    /// The change in the number of rockets: `1` if a rocket has been built,
+    /// `-1` if it has been fired (or destroyed), `0` otherwise.
+    pub rocket_delta: i8,
+}
+
+impl StateDiff {
+    /// Returns `true` if nothing changed between the two snapshots.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty() && self.rocket_delta == 0
+    }
+}
+
+/// Outcome of a single [`Planet::poll_once`] call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollOutcome {
+    /// No message was ready on the planet channels, nothing has been done.
+    Idle,
+    /// One message has been received and handled.
+    Processed,
+    /// The planet has been killed (or destroyed itself, see [`Planet::with_self_destruct`])
+    /// and must not be polled anymore.
+    Killed,
+}
+
+/// Why [`Planet::run`] terminated normally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// The planet has been killed by the orchestrator, or destroyed itself after a failed
+    /// asteroid defense (see [`Planet::with_self_destruct`]).
+    Killed,
+    /// The orchestrator disconnected from one of the planet channels, e.g. during shutdown.
+    OrchestratorDisconnected,
+}
+
+/// Errors that terminate the planet message loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanetError {
+    /// The orchestrator disconnected from one of the planet channels.
+    OrchestratorDisconnected,
+    /// A response could not be delivered to the explorer with the given id.
+    ExplorerDisconnected(ID),
+    /// A message could not be sent to the orchestrator before the send timeout expired
+    /// (see [`Planet::with_orchestrator_send_timeout`]), as its channel stayed full.
+    OrchestratorBackpressure,
+}
+
+impl Display for PlanetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanetError::OrchestratorDisconnected => write!(f, "Orchestrator disconnected."),
+            PlanetError::ExplorerDisconnected(id) => write!(f, "Explorer {id} disconnected."),
+            PlanetError::OrchestratorBackpressure => {
+                write!(f, "Orchestrator channel full, send timed out.")
+            }
+        }
+    }
+}
+
+impl Error for PlanetError {}
+
+// number of messages buffered for each explorer before they're flushed:
+// with a single slot every message is delivered as soon as it's sent
+const EXPLORER_BUFFER_SIZE: usize = 1;
+
+// Outbound channel towards an explorer visiting the planet: every message
+// to an explorer goes through here, buffered up to `capacity` messages.
+struct ExplorerChannel {
+    sender: Sender<PlanetToExplorer>,
+    buffer: VecDeque<PlanetToExplorer>,
+    capacity: usize,
+}
+
+impl ExplorerChannel {
+    fn new(sender: Sender<PlanetToExplorer>) -> Self {
+        ExplorerChannel {
+            sender,
+            buffer: VecDeque::with_capacity(EXPLORER_BUFFER_SIZE),
+            capacity: EXPLORER_BUFFER_SIZE,
+        }
+    }
+
+    // queues the message, flushing the buffer once it's full
+    fn send(&mut self, msg: PlanetToExplorer) -> Result<(), SendError<PlanetToExplorer>> {
+        self.buffer.push_back(msg);
+        if self.buffer.len() >= self.capacity {
+            self.flush()
+        } else {
+            Ok(())
+        }
+    }
+
+    // delivers all the buffered messages, in order: if the explorer
+    // disconnected, the remaining ones are discarded
+    fn flush(&mut self) -> Result<(), SendError<PlanetToExplorer>> {
+        while let Some(msg) = self.buffer.pop_front() {
+            if let Err(err) = self.sender.send(msg) {
+                self.buffer.clear();
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Main, top-level planet definition. This type is built on top of
@@ -447,12 +1145,23 @@ pub struct Planet {
     from_orchestrator: Receiver<OrchestratorToPlanet>,
     to_orchestrator: Sender<PlanetToOrchestrator>,
     from_explorers: Receiver<ExplorerToPlanet>,
-    to_explorers: HashMap<ID, Sender<PlanetToExplorer>>,
+    to_explorers: HashMap<ID, ExplorerChannel>,
+
+    // `None` while the AI is running
+    stopped: Option<StopReason>,
+    // mirrors `stopped` for observers on other threads
+    running: Arc<AtomicBool>,
+    // whether to send `CapabilitiesAnnounce` after `on_start`
+    announce_capabilities: bool,
+    // whether explorer messages are served before orchestrator ones
+    explorer_priority: bool,
+    // whether to terminate after a failed asteroid defense and send `Destroyed`
+    self_destruct: bool,
+    // how long to wait for room on a full orchestrator channel, if not forever
+    orchestrator_send_timeout: Option<Duration>,
 }
 
 impl Planet {
-    const ORCH_DISCONNECT_ERR: &'static str = "Orchestrator disconnected.";
-
     /// Constructor for the [Planet] type.
     ///
     /// # Errors
@@ -479,40 +1188,36 @@ impl Planet {
     ) -> Result<Planet, String> {
         let PlanetConstraints {
             n_energy_cells,
-            unbounded_gen_rules,
             can_have_rocket,
-            n_comb_rules,
+            ..
         } = type_.constraints();
         let (from_orchestrator, to_orchestrator) = orchestrator_channels;
 
-        if gen_rules.is_empty() {
-            Err("gen_rules is empty".to_string())
-        } else if !unbounded_gen_rules && gen_rules.len() > 1 {
-            Err(format!(
-                "Too many generation rules (Planet type {type_:?} is limited to 1)"
-            ))
-        } else if comb_rules.len() > n_comb_rules {
-            Err(format!(
-                "Too many combination rules (Planet type {type_:?} is limited to {n_comb_rules})"
-            ))
+        if let Err(errors) = type_.validate_rules(&gen_rules, &comb_rules) {
+            Err(errors.join("; "))
         } else {
             let mut generator = Generator::new();
             let mut combinator = Combinator::new();
 
             // add gen and comb rules to the planet generator and combinator
             for r in gen_rules {
-                let _ = generator.add(r);
+                generator.add(r)?;
             }
             for r in comb_rules {
-                let _ = combinator.add(r);
+                combinator.add(r)?;
             }
 
             Ok(Planet {
                 state: PlanetState {
                     id,
+                    name: None,
                     energy_cells: (0..n_energy_cells).map(|_| EnergyCell::new()).collect(),
                     can_have_rocket,
                     rocket: None,
+                    resources: Vec::new(),
+                    present_explorers: HashSet::new(),
+                    max_explorers: None,
+                    last_activity: Instant::now(),
                 },
                 type_,
                 ai,
@@ -522,137 +1227,364 @@ impl Planet {
                 to_orchestrator,
                 from_explorers: explorers_receiver,
                 to_explorers: HashMap::new(),
+                stopped: Some(StopReason::NeverStarted),
+                running: Arc::new(AtomicBool::new(false)),
+                announce_capabilities: false,
+                explorer_priority: false,
+                self_destruct: false,
+                orchestrator_send_timeout: None,
             })
         }
     }
 
-    // Extracted helper to reduce the size of `run` and keep Clippy happy.
-    // Returns `Ok(Some(true))` when the planet should exit (killed),
-    // `Ok(None)` to continue running, or `Err` on channel errors.
+    /// Shares the planet running status through the given flag, which is then kept up to date
+    /// by the planet on every start/stop transition: `true` while the AI is running, `false`
+    /// while it is stopped (or before the first start) and after the planet has been killed.
+    ///
+    /// The flag is written with [`Ordering::Release`] and should be read with [`Ordering::Acquire`],
+    /// so that an observer seeing a transition also sees the effects of the handler that caused it
+    /// (e.g. [`PlanetAI::on_start`]).
+    #[must_use]
+    pub fn with_running_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        flag.store(self.stopped.is_none(), Ordering::Release);
+        self.running = flag;
+        self
+    }
+
+    /// Makes the planet send a [`PlanetToOrchestrator::CapabilitiesAnnounce`] with its supported
+    /// resources and combinations every time it starts, right after [`PlanetAI::on_start`].
+    ///
+    /// Disabled by default.
+    #[must_use]
+    pub fn with_capabilities_announce(mut self, enabled: bool) -> Self {
+        self.announce_capabilities = enabled;
+        self
+    }
+
+    /// Gives the planet a human-readable name (e.g. `"Alpha Centauri"`), for logs and GUIs
+    /// to show instead of its id. It is reported in [`DummyPlanetState::name`].
+    ///
+    /// By default, planets have no name.
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.state.name = Some(name.into());
+        self
+    }
+
+    /// Limits the number of explorers the planet accepts at once: when `max` explorers are on
+    /// the planet, an [`OrchestratorToPlanet::IncomingExplorerRequest`] is answered with an error
+    /// and the explorer is kept out, unless the AI decides otherwise (see [`PlanetAI::accept_explorer`]).
+    ///
+    /// By default, there is no limit.
+    #[must_use]
+    pub fn with_max_explorers(mut self, max: usize) -> Self {
+        self.state.max_explorers = Some(max);
+        self
+    }
+
+    /// Makes the planet serve explorer messages **before** orchestrator messages
+    /// when both are ready (see [`Planet::poll_once`] for the default priority).
+    ///
+    /// Disabled by default.
+    #[must_use]
+    pub fn with_explorer_priority(mut self, enabled: bool) -> Self {
+        self.explorer_priority = enabled;
+        self
+    }
+
+    /// Makes the planet terminate by itself when it fails to deflect an asteroid (the AI returns
+    /// no rocket from [`PlanetAI::handle_asteroid`]), right after the
+    /// [`PlanetToOrchestrator::AsteroidAck`], instead of waiting for a
+    /// [`OrchestratorToPlanet::KillPlanet`].
+    ///
+    /// Every termination, by asteroid or by kill, is then notified with a
+    /// [`PlanetToOrchestrator::Destroyed`] telling its cause.
+    ///
+    /// Disabled by default.
+    #[must_use]
+    pub fn with_self_destruct(mut self, enabled: bool) -> Self {
+        self.self_destruct = enabled;
+        self
+    }
+
+    /// Sets how long the planet waits for room on the orchestrator channel when it is bounded and
+    /// full, before giving up with [`PlanetError::OrchestratorBackpressure`] instead of blocking
+    /// forever.
+    ///
+    /// The message that could not be sent is dropped (with a warning), and the error ends
+    /// [`Planet::run`]: only opt in if a stalled orchestrator should terminate the planet.
+    ///
+    /// Disabled by default: the planet blocks until there is room. Unbounded channels are never
+    /// full, so this has no effect on them.
+    #[must_use]
+    pub fn with_orchestrator_send_timeout(mut self, timeout: Duration) -> Self {
+        self.orchestrator_send_timeout = Some(timeout);
+        self
+    }
+
+    /// Charges the first `n` energy cells of the planet, so that it can start with some cells
+    /// already charged (e.g. to set up deterministic scenarios).
+    ///
+    /// `n` is clamped to the number of cells of the planet.
+    #[must_use]
+    pub fn with_initial_charged_cells(mut self, n: usize) -> Self {
+        for cell in self.state.energy_cells.iter_mut().take(n) {
+            cell.charge(Sunray::new());
+        }
+        self
+    }
+
+    /// Replaces the planet AI with `new_ai`, returning the previous one.
+    ///
+    /// If the planet is *running*, [`PlanetAI::on_stop`] is invoked on the old AI (its answer is
+    /// ignored, the swap can't be vetoed) and [`PlanetAI::on_start`] on the new one, so that both
+    /// see a consistent lifecycle. If the planet is *stopped*, the AIs are just swapped and the new
+    /// one will be started by the next [`OrchestratorToPlanet::StartPlanetAI`].
+    ///
+    /// Since [`Planet::run`] borrows the planet for its whole duration, this can only be called
+    /// **before** the planet is moved to its thread (or between [`Planet::poll_once`] calls).
+    pub fn replace_ai(&mut self, new_ai: Box<dyn PlanetAI>) -> Box<dyn PlanetAI> {
+        let running = self.is_running();
+        if running {
+            self.ai
+                .on_stop(&self.state, &self.generator, &self.combinator);
+        }
+
+        let old_ai = std::mem::replace(&mut self.ai, new_ai);
+
+        if running {
+            self.ai
+                .on_start(&self.state, &self.generator, &self.combinator);
+        }
+        old_ai
+    }
+
+    /// Returns `true` if the planet AI is running, `false` if it's stopped.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.stopped.is_none()
+    }
+
+    // private helper updating the planet status and the shared running flag
+    fn set_stopped(&mut self, stopped: Option<StopReason>) {
+        self.stopped = stopped;
+        self.running.store(stopped.is_none(), Ordering::Release);
+    }
+
+    // Extracted helper to reduce the size of `poll_once` and keep Clippy happy.
+    // Handles a message received while the AI is *running*.
     fn handle_orchestrator_msg(
         &mut self,
         msg: OrchestratorToPlanet,
-    ) -> Result<Option<bool>, String> {
+    ) -> Result<PollOutcome, PlanetError> {
+        // liveness checks are answered before any AI call, in any state
+        if let OrchestratorToPlanet::Ping = msg {
+            self.send_orchestrator(PlanetToOrchestrator::Pong {
+                planet_id: self.id(),
+            })?;
+            return Ok(PollOutcome::Processed);
+        }
+
+        if let Some(reason) = self.stopped {
+            return self.handle_stopped_orchestrator_msg(&msg, reason);
+        }
+
+        if self.decline_orchestrator_msg(&msg)? {
+            return Ok(PollOutcome::Processed);
+        }
+
         match msg {
-            OrchestratorToPlanet::StartPlanetAI => Ok(None),
+            // start messages received while running are ignored,
+            // pings have already been answered
+            OrchestratorToPlanet::StartPlanetAI | OrchestratorToPlanet::Ping => {}
 
             OrchestratorToPlanet::StopPlanetAI => {
-                self.to_orchestrator
-                    .send(PlanetToOrchestrator::StopPlanetAIResult {
+                if self
+                    .ai
+                    .on_stop(&self.state, &self.generator, &self.combinator)
+                {
+                    self.set_stopped(Some(StopReason::ManualStop));
+                    self.send_orchestrator(PlanetToOrchestrator::StopPlanetAIResult {
                         planet_id: self.id(),
-                    })
-                    .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
-
-                self.ai
-                    .on_stop(&self.state, &self.generator, &self.combinator);
-
-                let kill = self.wait_for_start()?; // blocking wait
-                if kill {
-                    return Ok(Some(true));
+                    })?;
+                } else {
+                    self.send_orchestrator(PlanetToOrchestrator::StopDeferred {
+                        planet_id: self.id(),
+                    })?;
                 }
+            }
 
-                // restart AI
+            OrchestratorToPlanet::ResetPlanetAI => {
                 self.ai
-                    .on_start(&self.state, &self.generator, &self.combinator);
-                Ok(None)
+                    .on_reset(&self.state, &self.generator, &self.combinator);
+
+                self.send_orchestrator(PlanetToOrchestrator::ResetPlanetAIResult {
+                    planet_id: self.id(),
+                })?;
             }
 
             OrchestratorToPlanet::KillPlanet => {
-                self.to_orchestrator
-                    .send(PlanetToOrchestrator::KillPlanetResult {
-                        planet_id: self.id(),
-                    })
-                    .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
-
-                Ok(Some(true))
+                self.send_orchestrator(PlanetToOrchestrator::KillPlanetResult {
+                    planet_id: self.id(),
+                })?;
+                return self.terminate(DestroyCause::Killed);
             }
 
             OrchestratorToPlanet::Sunray(sunray) => {
                 self.ai
                     .handle_sunray(&mut self.state, &self.generator, &self.combinator, sunray);
 
-                self.to_orchestrator
-                    .send(PlanetToOrchestrator::SunrayAck {
-                        planet_id: self.id(),
-                    })
-                    .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
-
-                Ok(None)
+                self.send_orchestrator(PlanetToOrchestrator::SunrayAck {
+                    planet_id: self.id(),
+                })?;
             }
 
-            OrchestratorToPlanet::Asteroid(_) => {
-                let rocket =
-                    self.ai
-                        .handle_asteroid(&mut self.state, &self.generator, &self.combinator);
+            OrchestratorToPlanet::SunrayBurst(sunrays) => self.handle_sunray_burst(sunrays)?,
 
-                self.to_orchestrator
-                    .send(PlanetToOrchestrator::AsteroidAck {
-                        planet_id: self.id(),
-                        rocket,
-                    })
-                    .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
+            OrchestratorToPlanet::Asteroid(_) => return self.handle_asteroid(),
 
-                Ok(None)
+            OrchestratorToPlanet::DisarmPlanet => {
+                let rocket = self.state.take_rocket();
+                self.send_orchestrator(PlanetToOrchestrator::DisarmResult {
+                    planet_id: self.id(),
+                    rocket,
+                })?;
             }
 
             OrchestratorToPlanet::IncomingExplorerRequest {
                 explorer_id,
                 new_sender,
-            } => {
-                self.to_explorers.insert(explorer_id, new_sender);
-                self.ai.on_explorer_arrival(
-                    &mut self.state,
-                    &self.generator,
-                    &self.combinator,
-                    explorer_id,
-                );
+            } => self.handle_explorer_arrival(explorer_id, new_sender)?,
 
-                self.to_orchestrator
-                    .send(PlanetToOrchestrator::IncomingExplorerResponse {
-                        planet_id: self.id(),
-                        explorer_id,
-                        res: Ok(()),
-                    })
-                    .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
-
-                Ok(None)
+            OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id } => {
+                self.handle_explorer_departure(explorer_id)?;
             }
 
-            OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id } => {
-                self.to_explorers.remove(&explorer_id);
-                self.ai.on_explorer_departure(
+            OrchestratorToPlanet::InternalStateRequest => {
+                let dummy_state = self.ai.handle_internal_state_req(
                     &mut self.state,
                     &self.generator,
                     &self.combinator,
-                    explorer_id,
                 );
 
-                self.to_orchestrator
-                    .send(PlanetToOrchestrator::OutgoingExplorerResponse {
+                self.send_orchestrator(PlanetToOrchestrator::InternalStateResponse {
+                    planet_id: self.id(),
+                    planet_state: dummy_state,
+                })?;
+            }
+        }
+
+        Ok(PollOutcome::Processed)
+    }
+
+    // Asks the AI whether to decline a message received while running, answering with a `Nack`
+    // if so. Returns `true` if the message has been declined.
+    fn decline_orchestrator_msg(
+        &mut self,
+        msg: &OrchestratorToPlanet,
+    ) -> Result<bool, PlanetError> {
+        let request_kind = OrchestratorToPlanetKind::from(msg);
+        if matches!(
+            request_kind,
+            OrchestratorToPlanetKind::Ping
+                | OrchestratorToPlanetKind::StartPlanetAI
+                | OrchestratorToPlanetKind::KillPlanet
+                | OrchestratorToPlanetKind::Asteroid
+        ) {
+            return Ok(false);
+        }
+
+        let Some(reason) =
+            self.ai
+                .decline_request(&self.state, &self.generator, &self.combinator, request_kind)
+        else {
+            return Ok(false);
+        };
+
+        self.send_orchestrator(PlanetToOrchestrator::Nack {
+            planet_id: self.id(),
+            request_kind,
+            reason,
+        })?;
+        Ok(true)
+    }
+
+    // Handles a message received while the AI is *stopped*: only
+    // `Start` and `Kill` are served, everything else gets `Stopped { reason }`
+    fn handle_stopped_orchestrator_msg(
+        &mut self,
+        msg: &OrchestratorToPlanet,
+        reason: StopReason,
+    ) -> Result<PollOutcome, PlanetError> {
+        match msg {
+            OrchestratorToPlanet::StartPlanetAI => {
+                self.send_orchestrator(PlanetToOrchestrator::StartPlanetAIResult {
+                    planet_id: self.id(),
+                })?;
+
+                self.ai
+                    .on_start(&self.state, &self.generator, &self.combinator);
+                self.set_stopped(None);
+
+                if self.announce_capabilities {
+                    self.send_orchestrator(PlanetToOrchestrator::CapabilitiesAnnounce {
                         planet_id: self.id(),
-                        explorer_id,
-                        res: Ok(()),
-                    })
-                    .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
+                        resources: self.generator.all_available_recipes(),
+                        combinations: self.combinator.all_available_recipes(),
+                    })?;
+                }
+            }
 
-                Ok(None)
+            OrchestratorToPlanet::KillPlanet => {
+                self.send_orchestrator(PlanetToOrchestrator::KillPlanetResult {
+                    planet_id: self.id(),
+                })?;
+                return self.terminate(DestroyCause::Killed);
             }
 
-            OrchestratorToPlanet::InternalStateRequest => {
-                let dummy_state = self.ai.handle_internal_state_req(
-                    &mut self.state,
-                    &self.generator,
-                    &self.combinator,
-                );
+            _ => {
+                self.send_orchestrator(PlanetToOrchestrator::Stopped {
+                    planet_id: self.id(),
+                    reason,
+                })?;
+            }
+        }
 
-                self.to_orchestrator
-                    .send(PlanetToOrchestrator::InternalStateResponse {
-                        planet_id: self.id(),
-                        planet_state: dummy_state,
-                    })
-                    .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
+        Ok(PollOutcome::Processed)
+    }
+
+    // private helper sending a message to the orchestrator, mapping a disconnected
+    // channel to the usual error and giving up (with a warning) if it stays full
+    // for longer than the send timeout, if any
+    fn send_orchestrator(&self, msg: PlanetToOrchestrator) -> Result<(), PlanetError> {
+        let Some(timeout) = self.orchestrator_send_timeout else {
+            return self
+                .to_orchestrator
+                .send(msg)
+                .map_err(|_| PlanetError::OrchestratorDisconnected);
+        };
 
-                Ok(None)
+        let kind = PlanetToOrchestratorKind::from(&msg);
+        match self.to_orchestrator.send_timeout(msg, timeout) {
+            Ok(()) => Ok(()),
+            Err(SendTimeoutError::Disconnected(_)) => Err(PlanetError::OrchestratorDisconnected),
+            Err(SendTimeoutError::Timeout(_)) => {
+                let mut payload = Payload::new();
+                payload.insert(
+                    "reason".to_string(),
+                    "orchestrator channel full, message dropped".to_string(),
+                );
+                payload.insert("timeout_ms".to_string(), timeout.as_millis().to_string());
+                LogEvent::new(
+                    Some(Participant::new(ActorType::Planet, self.id())),
+                    Some(Participant::new(ActorType::Orchestrator, 0u32)),
+                    kind.event_type(),
+                    Channel::Warning,
+                    payload,
+                )
+                .with_message_kind(kind)
+                .emit();
+                Err(PlanetError::OrchestratorBackpressure)
             }
         }
     }
@@ -663,104 +1595,294 @@ impl Planet {
     /// to the different messages.
     ///
     /// This method is *blocking* and should be called by the orchestrator in a separate thread.
-    /// It returns with an empty [Ok] when the planet has been **killed** (destroyed).
+    /// It returns with [Ok] when the planet terminates normally, telling why (see [`RunOutcome`]):
+    /// it has been **killed** (destroyed) or the orchestrator disconnected (e.g. during shutdown).
+    /// To drive the planet without a dedicated thread, see [`Planet::poll_once`].
     ///
     /// # Errors
-    /// If the orchestrator disconnects from the channel, this will return an [Err].
-    pub fn run(&mut self) -> Result<(), String> {
-        // run the planet stopped by default
-        // and wait for a StartPlanetAI message
-        let kill = self.wait_for_start()?;
-        if kill {
-            return Ok(());
+    /// Returns an [Err] describing the fault if the loop stops for any other reason
+    /// (see [`PlanetError`]).
+    pub fn run(&mut self) -> Result<RunOutcome, String> {
+        loop {
+            match self.poll_once() {
+                Ok(PollOutcome::Killed) => return Ok(RunOutcome::Killed),
+                Ok(PollOutcome::Processed) => {}
+                Ok(PollOutcome::Idle) => self.wait_for_message(),
+                Err(PlanetError::OrchestratorDisconnected) => {
+                    return Ok(RunOutcome::OrchestratorDisconnected);
+                }
+                Err(err) => return Err(err.to_string()),
+            }
         }
+    }
+
+    /// Handles **at most one** message among the ones ready on the planet channels, without blocking.
+    ///
+    /// This is the building block of [`Planet::run`]: calling it repeatedly until it returns
+    /// [`PollOutcome::Killed`] has the same effect, which allows a cooperative scheduler to drive
+    /// many planets from a single thread. Like in [`Planet::run`], the planet starts in a *stopped*
+    /// state.
+    ///
+    /// # Priority
+    /// By default, orchestrator messages are **always** served before explorer messages: an explorer
+    /// message is only handled when no orchestrator message is ready, so explorers can be starved by
+    /// a steady orchestrator traffic. Planets built with [`Planet::with_explorer_priority`] reverse
+    /// the order (and may starve the orchestrator instead).
+    ///
+    /// # Returns
+    /// - [`PollOutcome::Idle`] if no message was ready.
+    /// - [`PollOutcome::Processed`] if one message has been handled.
+    /// - [`PollOutcome::Killed`] if the planet has been killed: it must not be polled anymore.
+    ///
+    /// # Errors
+    /// - [`PlanetError::OrchestratorDisconnected`] if the orchestrator disconnected from the channel.
+    /// - [`PlanetError::ExplorerDisconnected`] if a response could not be delivered to an explorer.
+    /// - [`PlanetError::OrchestratorBackpressure`] if the orchestrator channel stayed full
+    ///   (see [`Planet::with_orchestrator_send_timeout`]).
+    pub fn poll_once(&mut self) -> Result<PollOutcome, PlanetError> {
+        let outcome = if self.explorer_priority {
+            match self.poll_explorers()? {
+                Some(outcome) => Some(outcome),
+                None => self.poll_orchestrator()?,
+            }
+        } else {
+            match self.poll_orchestrator()? {
+                Some(outcome) => Some(outcome),
+                None => self.poll_explorers()?,
+            }
+        };
 
-        self.ai
-            .on_start(&self.state, &self.generator, &self.combinator);
+        Ok(outcome.unwrap_or(PollOutcome::Idle))
+    }
 
-        loop {
-            select_biased! {
-                // wait for orchestrator message (prioritized operation)
-                recv(self.from_orchestrator) -> msg => match msg {
-                    Ok(m) => {
-                        if let Some(true) = self.handle_orchestrator_msg(m)? {
-                            return Ok(());
-                        }
-                    }
+    /// Takes all the orchestrator messages that are ready on the channel, without blocking
+    /// and **without handling them**.
+    ///
+    /// This lets a cooperative scheduler pull a burst of messages at once: each of them must
+    /// still be handed to the planet with [`Planet::process_orchestrator_msg`], in order,
+    /// as the orchestrator waits for the responses (and the resources or channels the
+    /// messages carry are only moved out of the channel).
+    ///
+    /// A disconnected channel just ends the burst, it will be reported by the next poll.
+    pub fn drain_orchestrator(&mut self) -> Vec<OrchestratorToPlanet> {
+        self.from_orchestrator.try_iter().collect()
+    }
 
-                    Err(_) => {
-                        return Err(Self::ORCH_DISCONNECT_ERR.to_string())
-                    }
-                },
+    /// Handles an orchestrator message previously taken with [`Planet::drain_orchestrator`],
+    /// exactly like [`Planet::poll_once`] would have.
+    ///
+    /// # Returns
+    /// - [`PollOutcome::Processed`] if the message has been handled.
+    /// - [`PollOutcome::Killed`] if the planet has been killed: it must not be polled anymore.
+    ///
+    /// # Errors
+    /// [`PlanetError::OrchestratorDisconnected`] or [`PlanetError::OrchestratorBackpressure`]
+    /// if the response could not be delivered.
+    pub fn process_orchestrator_msg(
+        &mut self,
+        msg: OrchestratorToPlanet,
+    ) -> Result<PollOutcome, PlanetError> {
+        // monitoring messages are not activity, or they would always observe a busy planet
+        if !matches!(
+            msg,
+            OrchestratorToPlanet::Ping | OrchestratorToPlanet::InternalStateRequest
+        ) {
+            self.state.last_activity = Instant::now();
+        }
+        self.handle_orchestrator_msg(msg)
+    }
 
-                // wait for explorer message (ignore disconnections)
-                recv(self.from_explorers) -> msg => if let Ok(msg) = msg {
-                    let explorer_id = msg.explorer_id();
-
-                    // if requesting explorer is currently
-                    // on the planet respond to it
-                    if let Some(to_explorer) = self.to_explorers.get(&explorer_id)
-                        && let Some(response) = self.ai.handle_explorer_msg(
-                            &mut self.state,
-                            &self.generator,
-                            &self.combinator,
-                            msg,
-                        )
-                    {
-                        to_explorer
-                            .send(response)
-                            .map_err(|_| format!("Explorer {explorer_id} disconnected."))?;
-                    }
-                }
+    // private helper handling an orchestrator message, if one is ready
+    fn poll_orchestrator(&mut self) -> Result<Option<PollOutcome>, PlanetError> {
+        match self.from_orchestrator.try_recv() {
+            Ok(msg) => self.process_orchestrator_msg(msg).map(Some),
+            Err(TryRecvError::Disconnected) => Err(PlanetError::OrchestratorDisconnected),
+            Err(TryRecvError::Empty) => Ok(None),
+        }
+    }
+
+    // private helper handling an explorer message, if one is ready (ignores disconnections)
+    fn poll_explorers(&mut self) -> Result<Option<PollOutcome>, PlanetError> {
+        match self.from_explorers.try_recv() {
+            Ok(msg) => {
+                self.state.last_activity = Instant::now();
+                self.handle_explorer_msg(msg)?;
+                Ok(Some(PollOutcome::Processed))
             }
+            Err(_) => Ok(None),
         }
     }
 
-    // private helper function that blocks until
-    // a StartPlanetAI message is received
-    fn wait_for_start(&self) -> Result<bool, String> {
-        loop {
-            select_biased! {
-                // orch messages
-                recv(self.from_orchestrator) -> msg => match msg {
-                    // if `Start` is received, return false
-                    Ok(OrchestratorToPlanet::StartPlanetAI) => {
-                        self.to_orchestrator
-                            .send(PlanetToOrchestrator::StartPlanetAIResult {
-                                planet_id: self.id(),
-                            })
-                            .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
-
-                        return Ok(false);
-                    }
-                    // if `Kill` is received, return true
-                    Ok(OrchestratorToPlanet::KillPlanet) => {
-                        self.to_orchestrator
-                            .send(PlanetToOrchestrator::KillPlanetResult { planet_id: self.id() })
-                            .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
+    // private helper that blocks until a message is ready
+    // on any of the planet channels, without receiving it
+    fn wait_for_message(&self) {
+        let mut sel = Select::new();
+        sel.recv(&self.from_orchestrator);
+        sel.recv(&self.from_explorers);
+        sel.ready();
+    }
 
-                        return Ok(true)
-                    }
-                    // every other message we respond with `Stopped`
-                    Ok(_) => {
-                        self.to_orchestrator
-                            .send(PlanetToOrchestrator::Stopped {
-                                planet_id: self.id(),
-                            })
-                            .map_err(|_| Self::ORCH_DISCONNECT_ERR.to_string())?;
-                    }
+    // private helper that forwards an explorer message to the AI
+    // and delivers the optional response back to the explorer
+    fn handle_explorer_msg(&mut self, msg: ExplorerToPlanet) -> Result<(), PlanetError> {
+        let explorer_id = msg.explorer_id();
 
-                    Err(_) => return Err(Self::ORCH_DISCONNECT_ERR.to_string()),
-                },
+        // if requesting explorer is currently
+        // on the planet respond to it
+        let Some(to_explorer) = self.to_explorers.get_mut(&explorer_id) else {
+            self.warn_dropped_explorer_msg(&msg);
+            return Ok(());
+        };
 
-                // explorers messages
-                recv(self.from_explorers) -> msg => if let Ok(msg) = msg &&
-                    let Some(to_explorer) = self.to_explorers.get(&msg.explorer_id())
-                {
-                    let _ = to_explorer.send(PlanetToExplorer::Stopped);
-                }
-            }
+        // a stopped planet only acknowledges the message
+        if let Some(reason) = self.stopped {
+            let _ = to_explorer.send(PlanetToExplorer::Stopped { reason });
+            return Ok(());
+        }
+
+        if let Some(response) =
+            self.ai
+                .handle_explorer_msg(&mut self.state, &self.generator, &self.combinator, msg)
+            && let Some(to_explorer) = self.to_explorers.get_mut(&explorer_id)
+            && to_explorer.send(response).is_err()
+        {
+            // the explorer is gone: its further messages must not reach the AI
+            self.to_explorers.remove(&explorer_id);
+            self.state.present_explorers.remove(&explorer_id);
+            return Err(PlanetError::ExplorerDisconnected(explorer_id));
+        }
+
+        Ok(())
+    }
+
+    // private helper that asks the AI for a rocket and acks the asteroid,
+    // terminating the planet after a failed defense if it self-destructs
+    fn handle_asteroid(&mut self) -> Result<PollOutcome, PlanetError> {
+        let rocket = self
+            .ai
+            .handle_asteroid(&mut self.state, &self.generator, &self.combinator);
+        let outcome = AsteroidOutcome::from_rocket(rocket.as_ref());
+
+        self.send_orchestrator(PlanetToOrchestrator::AsteroidAck {
+            planet_id: self.id(),
+            outcome,
+            rocket,
+        })?;
+
+        if self.self_destruct && outcome == AsteroidOutcome::Destroyed {
+            self.terminate(DestroyCause::Asteroid)
+        } else {
+            Ok(PollOutcome::Processed)
+        }
+    }
+
+    // private helper marking the planet as terminated,
+    // notifying the orchestrator if the planet self-destructs
+    fn terminate(&mut self, cause: DestroyCause) -> Result<PollOutcome, PlanetError> {
+        self.running.store(false, Ordering::Release);
+        if self.self_destruct {
+            self.send_orchestrator(PlanetToOrchestrator::Destroyed {
+                planet_id: self.id(),
+                cause,
+            })?;
+        }
+        Ok(PollOutcome::Killed)
+    }
+
+    // private helper that hands a sunray burst to the AI and acks it,
+    // counting as wasted the sunrays the AI didn't use
+    fn handle_sunray_burst(&mut self, sunrays: Vec<Sunray>) -> Result<(), PlanetError> {
+        let n = sunrays.len();
+        let charged = self
+            .ai
+            .handle_sunray_burst(&mut self.state, &self.generator, &self.combinator, sunrays)
+            .min(n);
+
+        self.send_orchestrator(PlanetToOrchestrator::SunrayBurstAck {
+            planet_id: self.id(),
+            charged,
+            wasted: n - charged,
+        })
+    }
+
+    // private helper that opens the channel to an incoming explorer
+    // and notifies both the AI and the orchestrator
+    fn handle_explorer_arrival(
+        &mut self,
+        explorer_id: ID,
+        sender: Sender<PlanetToExplorer>,
+    ) -> Result<(), PlanetError> {
+        if !self.state.is_explorer_present(explorer_id)
+            && !self
+                .ai
+                .accept_explorer(&self.state, &self.generator, &self.combinator, explorer_id)
+        {
+            return self.send_orchestrator(PlanetToOrchestrator::IncomingExplorerResponse {
+                planet_id: self.id(),
+                explorer_id,
+                res: Err("planet full".to_string()),
+            });
+        }
+
+        self.to_explorers
+            .insert(explorer_id, ExplorerChannel::new(sender));
+        self.state.present_explorers.insert(explorer_id);
+        self.ai.on_explorer_arrival(
+            &mut self.state,
+            &self.generator,
+            &self.combinator,
+            explorer_id,
+        );
+
+        self.send_orchestrator(PlanetToOrchestrator::IncomingExplorerResponse {
+            planet_id: self.id(),
+            explorer_id,
+            res: Ok(()),
+        })
+    }
+
+    // private helper that closes the channel to a departing explorer,
+    // delivering whatever is still buffered before letting it go,
+    // and notifies both the AI and the orchestrator
+    fn handle_explorer_departure(&mut self, explorer_id: ID) -> Result<(), PlanetError> {
+        if let Some(mut channel) = self.to_explorers.remove(&explorer_id) {
+            let _ = channel.flush();
         }
+        self.state.present_explorers.remove(&explorer_id);
+        self.ai.on_explorer_departure(
+            &mut self.state,
+            &self.generator,
+            &self.combinator,
+            explorer_id,
+        );
+
+        self.send_orchestrator(PlanetToOrchestrator::OutgoingExplorerResponse {
+            planet_id: self.id(),
+            explorer_id,
+            res: Ok(()),
+        })
+    }
+
+    // private helper that surfaces a message coming from an explorer which
+    // is not (or no longer) on the planet, since it is dropped unanswered
+    fn warn_dropped_explorer_msg(&self, msg: &ExplorerToPlanet) {
+        let kind = ExplorerToPlanetKind::from(msg);
+        let mut payload = Payload::new();
+        payload.insert("explorer_id".to_string(), msg.explorer_id().to_string());
+        payload.insert(
+            "reason".to_string(),
+            "explorer is not on the planet, message dropped".to_string(),
+        );
+
+        LogEvent::new(
+            Some(Participant::new(ActorType::Explorer, msg.explorer_id())),
+            Some(Participant::new(ActorType::Planet, self.id())),
+            kind.event_type(),
+            Channel::Warning,
+            payload,
+        )
+        .with_message_kind(kind)
+        .emit();
     }
 
     /// Returns the planet id.
@@ -775,6 +1897,64 @@ impl Planet {
         self.type_
     }
 
+    /// Returns the number of energy cells the planet owns, as set by its [`PlanetType`].
+    #[must_use]
+    pub fn energy_cell_capacity(&self) -> usize {
+        self.state.cells_count()
+    }
+
+    /// Returns the maximum number of rockets the planet can store at once,
+    /// as set by its [`PlanetType`] (`0` if it can't build rockets).
+    #[must_use]
+    pub fn max_rockets(&self) -> usize {
+        usize::from(self.state.can_have_rocket())
+    }
+
+    /// Returns how many resources of each basic type the planet could generate right now,
+    /// if it spent **all** its charged energy cells on generation (one cell per resource).
+    ///
+    /// The cells are split as evenly as possible among the generation recipes: each recipe gets
+    /// `charged / recipes` cells, and the remaining ones go one each to the first recipes in the
+    /// [`BasicResourceType`] declaration order (see [`Generator::recipes_sorted`]). Every recipe
+    /// is in the map, possibly with a count of 0.
+    #[must_use]
+    pub fn max_generation_yield(&self) -> HashMap<BasicResourceType, u32> {
+        let recipes = self.generator.recipes_sorted();
+        let charged = self.state.charged_cells_count();
+        let (share, remainder) = if recipes.is_empty() {
+            (0, 0)
+        } else {
+            (charged / recipes.len(), charged % recipes.len())
+        };
+
+        recipes
+            .into_iter()
+            .enumerate()
+            .map(|(i, basic)| {
+                let count = share + usize::from(i < remainder);
+                (basic, u32::try_from(count).unwrap_or(u32::MAX))
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the planet could generate `n` resources of type `t` right now:
+    /// it has the recipe and at least `n` charged energy cells (one per resource).
+    #[must_use]
+    pub fn can_generate(&self, t: BasicResourceType, n: usize) -> bool {
+        self.generator.contains(t) && self.state.has_charged_cells(n)
+    }
+
+    /// Returns the number of explorer messages queued on the planet channel, not handled yet.
+    ///
+    /// Since [`Planet::run`] borrows the planet for its whole life, this is only available to
+    /// whoever owns the planet outside of it: before the planet is spawned, or between
+    /// [`Planet::poll_once`] calls. Messages from explorers that are not on the planet are counted
+    /// too, even though they will be dropped.
+    #[must_use]
+    pub fn pending_explorer_count(&self) -> usize {
+        self.from_explorers.len()
+    }
+
     /// Returns an immutable borrow of planet's internal state.
     #[must_use]
     pub fn state(&self) -> &PlanetState {
@@ -792,6 +1972,75 @@ impl Planet {
     pub fn combinator(&self) -> &Combinator {
         &self.combinator
     }
+
+    /// Returns how many more generation rules the planet may have for its [`PlanetType`]
+    /// (see [`PlanetType::max_gen_rules`]), or `None` if they are unbounded.
+    #[must_use]
+    pub fn remaining_gen_budget(&self) -> Option<usize> {
+        self.type_
+            .max_gen_rules()
+            .map(|max| max.saturating_sub(self.generator.len()))
+    }
+
+    /// Returns how many more combination rules the planet may have for its [`PlanetType`]
+    /// (see [`PlanetType::max_comb_rules`]).
+    #[must_use]
+    pub fn remaining_comb_budget(&self) -> usize {
+        self.type_
+            .max_comb_rules()
+            .saturating_sub(self.combinator.len())
+    }
+
+    /// Checks that the planet recipes are consistent: besides the constraints of the
+    /// planet type (see [`PlanetType::validate_rules`]), every combination rule must be usable
+    /// with what the planet can generate or already stores, directly or through its other
+    /// combination rules.
+    ///
+    /// A *dead* combination rule (e.g. `Water` on a planet generating neither `Hydrogen` nor
+    /// `Oxygen`) can still be used with resources brought by explorers, so this is advisory.
+    ///
+    /// # Errors
+    /// Returns a [Vec] with a description of each problem found.
+    pub fn validate_capabilities(&self) -> Result<(), Vec<String>> {
+        let mut errors = self
+            .type_
+            .validate_rules(
+                &self.generator.recipes_sorted(),
+                &self.combinator.recipes_sorted(),
+            )
+            .err()
+            .unwrap_or_default();
+
+        let seed = self
+            .generator
+            .all_available_recipes()
+            .into_iter()
+            .map(ResourceType::Basic)
+            .chain(self.state.resources_iter().map(GenericResource::get_type))
+            .collect();
+        let obtainable = reachable_from(seed, &self.combinator);
+
+        for complex in self.combinator.recipes_sorted() {
+            let (lhs, rhs) = complex.request_signature();
+            let missing: Vec<&str> = [lhs, rhs]
+                .iter()
+                .filter(|input| !obtainable.contains(input))
+                .map(ResourceType::name)
+                .collect();
+            if !missing.is_empty() {
+                errors.push(format!(
+                    "Dead combination rule {complex:?}: {} can't be obtained on the planet",
+                    missing.join(" and ")
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -803,23 +2052,32 @@ mod tests {
 
     use crate::components::asteroid::Asteroid;
     use crate::components::energy_cell::EnergyCell;
-    use crate::components::resource::{BasicResourceType, Combinator, Generator};
+    use crate::components::resource::{BasicResource, BasicResourceType, Combinator, Generator};
     use crate::components::rocket::Rocket;
     use crate::components::sunray::Sunray;
-    use crate::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
+    use crate::protocols::orchestrator_planet::{
+        OrchestratorToPlanet, PlanetToOrchestrator, PlanetToOrchestratorKind, broadcast_to_planets,
+    };
+    use crate::protocols::planet_explorer::{ExplorerToPlanetKind, PlanetToExplorerKind};
 
     // --- Mock AI ---
     struct MockAI {
-        start_called: bool,
-        stop_called: bool,
+        start_called: Arc<AtomicBool>,
+        stop_called: Arc<AtomicBool>,
+        reset_called: Arc<AtomicBool>,
+        veto_stop: bool,
+        declined: Option<OrchestratorToPlanetKind>,
         sunray_count: ID,
     }
 
     impl MockAI {
         fn new() -> Self {
             Self {
-                start_called: false,
-                stop_called: false,
+                start_called: Arc::new(AtomicBool::new(false)),
+                stop_called: Arc::new(AtomicBool::new(false)),
+                reset_called: Arc::new(AtomicBool::new(false)),
+                veto_stop: false,
+                declined: None,
                 sunray_count: 0,
             }
         }
@@ -867,15 +2125,31 @@ mod tests {
 
         fn handle_explorer_msg(
             &mut self,
-            _state: &mut PlanetState,
-            _generator: &Generator,
-            _combinator: &Combinator,
+            state: &mut PlanetState,
+            generator: &Generator,
+            combinator: &Combinator,
             msg: ExplorerToPlanet,
         ) -> Option<PlanetToExplorer> {
             match msg {
+                ExplorerToPlanet::SupportedResourceRequest { .. } => {
+                    Some(PlanetToExplorer::supported_resources_from(generator))
+                }
+                ExplorerToPlanet::SupportedCombinationRequest { .. } => {
+                    Some(PlanetToExplorer::supported_combinations_from(combinator))
+                }
                 ExplorerToPlanet::AvailableEnergyCellRequest { .. } => {
                     Some(PlanetToExplorer::AvailableEnergyCellResponse { available_cells: 5 })
                 }
+                ExplorerToPlanet::DepositResourceRequest { resource, .. } => {
+                    state.store_resource(resource);
+                    Some(PlanetToExplorer::DepositResourceResponse { res: Ok(()) })
+                }
+                ExplorerToPlanet::StoredResourcesRequest { .. } => {
+                    Some(PlanetToExplorer::stored_resources_from(state))
+                }
+                ExplorerToPlanet::AffordableCombinationsRequest { .. } => Some(
+                    PlanetToExplorer::affordable_combinations_from(state, combinator),
+                ),
                 _ => None,
             }
         }
@@ -886,7 +2160,7 @@ mod tests {
             _generator: &Generator,
             _combinator: &Combinator,
         ) {
-            self.start_called = true;
+            self.start_called.store(true, Ordering::SeqCst);
         }
 
         fn on_stop(
@@ -894,12 +2168,32 @@ mod tests {
             _state: &PlanetState,
             _generator: &Generator,
             _combinator: &Combinator,
+        ) -> bool {
+            self.stop_called.store(true, Ordering::SeqCst);
+            !self.veto_stop
+        }
+
+        fn on_reset(
+            &mut self,
+            _state: &PlanetState,
+            _generator: &Generator,
+            _combinator: &Combinator,
         ) {
-            self.stop_called = true;
+            self.reset_called.store(true, Ordering::SeqCst);
         }
-    }
 
-    // --- Helper for creating dummy channels ---
+        fn decline_request(
+            &mut self,
+            _state: &PlanetState,
+            _generator: &Generator,
+            _combinator: &Combinator,
+            request_kind: OrchestratorToPlanetKind,
+        ) -> Option<String> {
+            (self.declined == Some(request_kind)).then(|| "busy".to_string())
+        }
+    }
+
+    // --- Helper for creating dummy channels ---
     // Returns the halves required by Planet::new
     type PlanetOrchHalfChannels = (Receiver<OrchestratorToPlanet>, Sender<PlanetToOrchestrator>);
 
@@ -935,13 +2229,58 @@ mod tests {
 
     // --- Unit Tests: Planet State Logic ---
 
+    #[test]
+    fn test_generate_into_storage() {
+        let mut state = PlanetState {
+            id: 0,
+            name: None,
+            energy_cells: vec![EnergyCell::new()],
+            rocket: None,
+            can_have_rocket: false,
+            resources: Vec::new(),
+            present_explorers: HashSet::new(),
+            max_explorers: None,
+            last_activity: Instant::now(),
+        };
+        let mut generator = Generator::new();
+        generator.add(BasicResourceType::Oxygen).unwrap();
+
+        assert_eq!(
+            state.generate_into_storage(&generator, BasicResourceType::Oxygen),
+            Err(GenerateError::CellNotCharged)
+        );
+        state.cell_mut(0).charge(Sunray::new());
+        assert_eq!(
+            state.generate_into_storage(&generator, BasicResourceType::Carbon),
+            Err(GenerateError::MissingRecipe(BasicResourceType::Carbon))
+        );
+        assert!(state.cell(0).is_charged());
+
+        assert_eq!(
+            state.generate_into_storage(&generator, BasicResourceType::Oxygen),
+            Ok(())
+        );
+        assert!(!state.cell(0).is_charged());
+        assert!(
+            state
+                .resources_iter()
+                .all(|res| res.is_basic_type(BasicResourceType::Oxygen))
+        );
+        assert_eq!(state.stored_resources_count(), 1);
+    }
+
     #[test]
     fn test_planet_state_rocket_construction() {
         let mut state = PlanetState {
             id: 0,
+            name: None,
             energy_cells: vec![EnergyCell::new()],
             rocket: None,
             can_have_rocket: true,
+            resources: Vec::new(),
+            present_explorers: HashSet::new(),
+            max_explorers: None,
+            last_activity: Instant::now(),
         };
 
         let cell = state.cell_mut(0);
@@ -960,13 +2299,127 @@ mod tests {
         assert!(!state.has_rocket());
     }
 
+    #[test]
+    fn test_planet_state_build_rocket_from_any() {
+        let mut state = PlanetState {
+            id: 0,
+            name: None,
+            energy_cells: vec![EnergyCell::new(), EnergyCell::new()],
+            rocket: None,
+            can_have_rocket: true,
+            resources: Vec::new(),
+            present_explorers: HashSet::new(),
+            max_explorers: None,
+            last_activity: Instant::now(),
+        };
+
+        // no charged cell
+        assert!(state.build_rocket_from_any().is_err());
+        assert!(!state.has_rocket());
+
+        // the first charged cell is used, whatever its index
+        state.cell_mut(1).charge(Sunray::new());
+        assert!(state.build_rocket_from_any().is_ok());
+        assert!(state.has_rocket());
+        assert!(!state.cell(1).is_charged());
+
+        // a single rocket can be stored
+        state.cell_mut(0).charge(Sunray::new());
+        assert!(state.build_rocket_from_any().is_err());
+        assert!(state.cell(0).is_charged());
+
+        // planets that can't have rockets are refused even with a charged cell
+        state.can_have_rocket = false;
+        assert!(state.take_rocket().is_some());
+        assert!(state.build_rocket_from_any().is_err());
+        assert!(!state.has_rocket());
+        assert!(state.cell(0).is_charged());
+    }
+
+    #[test]
+    fn test_planet_state_indexed_cells_never_panic() {
+        let mut state = PlanetState {
+            id: 0,
+            name: None,
+            energy_cells: vec![EnergyCell::new(), EnergyCell::new()],
+            rocket: None,
+            can_have_rocket: false,
+            resources: Vec::new(),
+            present_explorers: HashSet::new(),
+            max_explorers: None,
+            last_activity: Instant::now(),
+        };
+
+        assert!(state.charge_cell_at(1, Sunray::new()).is_ok());
+        assert!(state.charge_cell_at(1, Sunray::new()).is_err());
+        assert!(state.charge_cell_at(2, Sunray::new()).is_err());
+
+        assert!(state.discharge_cell(0).is_err());
+        assert!(state.discharge_cell(1).is_ok());
+        assert!(state.discharge_cell(5).is_err());
+    }
+
+    #[test]
+    fn test_planet_state_can_afford() {
+        let mut state = PlanetState {
+            id: 0,
+            name: None,
+            energy_cells: (0..5).map(|_| EnergyCell::new()).collect(),
+            rocket: None,
+            can_have_rocket: false,
+            resources: Vec::new(),
+            present_explorers: HashSet::new(),
+            max_explorers: None,
+            last_activity: Instant::now(),
+        };
+        assert!(!state.can_afford(ComplexResourceType::Water));
+
+        for i in 0..3 {
+            state.cell_mut(i).charge(Sunray::new());
+        }
+        assert_eq!(state.charged_cells_count(), 3);
+        assert!(state.can_afford(ComplexResourceType::Water));
+        assert!(!state.can_afford(ComplexResourceType::Life));
+    }
+
+    #[test]
+    fn test_planet_state_resources_storage() {
+        let mut state = PlanetState {
+            id: 0,
+            name: None,
+            energy_cells: vec![EnergyCell::new()],
+            rocket: None,
+            can_have_rocket: false,
+            resources: Vec::new(),
+            present_explorers: HashSet::new(),
+            max_explorers: None,
+            last_activity: Instant::now(),
+        };
+        let water = ResourceType::Complex(ComplexResourceType::Water);
+        let oxygen = ResourceType::Basic(BasicResourceType::Oxygen);
+
+        state.store_resource(GenericResource::fabricate(oxygen));
+        state.store_resource(GenericResource::fabricate(water));
+        assert_eq!(state.to_dummy().stored_resources, 2);
+
+        assert!(state.take_resource(water).is_some());
+        assert!(state.take_resource(water).is_none());
+        assert_eq!(state.resources_iter().next().unwrap().get_type(), oxygen);
+        assert_eq!(state.stored_resources_count(), 1);
+    }
+
     #[test]
     fn test_planet_state_type_b_no_rocket() {
         let mut state = PlanetState {
             id: 0,
+            name: None,
             energy_cells: vec![EnergyCell::new()],
             rocket: None,
             can_have_rocket: false, // Type B
+            resources: Vec::new(),
+            present_explorers: HashSet::new(),
+            max_explorers: None,
+            last_activity: Instant::now(),
         };
 
         let cell = state.cell_mut(0);
@@ -1022,6 +2475,118 @@ mod tests {
         assert!(invalid_gen.is_err());
     }
 
+    #[test]
+    fn test_planet_capacity_per_type() {
+        // (type, energy cells, rockets)
+        let expected = [
+            (PlanetType::A, 5, 1),
+            (PlanetType::B, 1, 0),
+            (PlanetType::C, 1, 1),
+            (PlanetType::D, 5, 0),
+        ];
+
+        for (planet_type, cells, rockets) in expected {
+            let (orch_ch, expl_ch, _, _) = get_test_channels();
+            let planet = Planet::new(
+                1,
+                planet_type,
+                Box::new(MockAI::new()),
+                vec![BasicResourceType::Oxygen],
+                vec![],
+                orch_ch,
+                expl_ch.0,
+            )
+            .expect("Failed to create planet");
+
+            assert_eq!(planet.energy_cell_capacity(), cells, "{planet_type:?}");
+            assert_eq!(planet.max_rockets(), rockets, "{planet_type:?}");
+        }
+    }
+
+    #[test]
+    fn test_planet_construction_rejects_duplicates() {
+        // Type D has unbounded generation rules, so only the duplicate is wrong
+        let (orch_ch, expl_ch, _, _) = get_test_channels();
+        let duplicate_gen = Planet::new(
+            1,
+            PlanetType::D,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen, BasicResourceType::Oxygen],
+            vec![],
+            orch_ch,
+            expl_ch.0,
+        );
+        assert!(duplicate_gen.is_err_and(|err| err.contains("Duplicate")));
+
+        let (orch_ch, expl_ch, _, _) = get_test_channels();
+        let duplicate_comb = Planet::new(
+            1,
+            PlanetType::C,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![ComplexResourceType::Water, ComplexResourceType::Water],
+            orch_ch,
+            expl_ch.0,
+        );
+        assert!(duplicate_comb.is_err_and(|err| err.contains("Duplicate")));
+    }
+
+    #[test]
+    fn test_planet_type_rules_limits() {
+        assert_eq!(PlanetType::A.max_gen_rules(), Some(1));
+        assert_eq!(PlanetType::B.max_gen_rules(), None);
+        assert_eq!(PlanetType::C.max_gen_rules(), Some(1));
+        assert_eq!(PlanetType::D.max_gen_rules(), None);
+
+        assert_eq!(PlanetType::A.max_comb_rules(), 0);
+        assert_eq!(PlanetType::B.max_comb_rules(), 1);
+        assert_eq!(PlanetType::C.max_comb_rules(), 6);
+        assert_eq!(PlanetType::D.max_comb_rules(), 0);
+    }
+
+    #[test]
+    fn test_planet_type_from_char() {
+        for planet_type in [PlanetType::A, PlanetType::B, PlanetType::C, PlanetType::D] {
+            let c = planet_type.as_char();
+            assert_eq!(PlanetType::from_char(c), Some(planet_type));
+            assert_eq!(c.to_string().parse(), Ok(planet_type));
+        }
+        assert_eq!(PlanetType::from_char('c'), Some(PlanetType::C));
+        assert_eq!(PlanetType::from_char('E'), None);
+        assert_eq!(
+            "AB".parse::<PlanetType>(),
+            Err(ParsePlanetTypeError::UnknownType("AB".to_string()))
+        );
+        assert!("".parse::<PlanetType>().is_err());
+    }
+
+    #[test]
+    fn test_validate_rules_reports_all_violations() {
+        assert!(
+            PlanetType::C
+                .validate_rules(
+                    &[BasicResourceType::Carbon],
+                    &[ComplexResourceType::Water, ComplexResourceType::Diamond]
+                )
+                .is_ok()
+        );
+
+        let errors = PlanetType::A
+            .validate_rules(
+                &[BasicResourceType::Oxygen, BasicResourceType::Oxygen],
+                &[ComplexResourceType::Water],
+            )
+            .unwrap_err();
+        // too many gen rules, too many comb rules, duplicate gen rule
+        assert_eq!(errors.len(), 3);
+
+        let errors = PlanetType::B
+            .validate_rules(&[], &[ComplexResourceType::Life, ComplexResourceType::Life])
+            .unwrap_err();
+        // empty gen rules, too many comb rules, duplicate comb rule
+        assert_eq!(errors.len(), 3);
+    }
+
     // --- Integration Tests: Loop ---
 
     #[test]
@@ -1049,7 +2614,7 @@ mod tests {
             let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 let res = planet.run();
                 match res {
-                    Ok(()) => {}
+                    Ok(_) => {}
                     Err(err) => {
                         dbg!(err);
                     }
@@ -1057,6 +2622,17 @@ mod tests {
             }));
         });
 
+        // 0. Requests sent before the first start are answered with `NeverStarted`
+        tx_to_planet_orch
+            .send(OrchestratorToPlanet::InternalStateRequest)
+            .unwrap();
+        match rx_to_orch.recv_timeout(Duration::from_millis(200)) {
+            Ok(PlanetToOrchestrator::Stopped { reason, .. }) => {
+                assert_eq!(reason, StopReason::NeverStarted);
+            }
+            _ => panic!("Planet sent incorrect response"),
+        }
+
         // 1. Start AI
         tx_to_planet_orch
             .send(OrchestratorToPlanet::StartPlanetAI)
@@ -1081,6 +2657,17 @@ mod tests {
             panic!("Did not receive SunrayAck");
         }
 
+        // 2b. Reset AI (energy cells are kept)
+        tx_to_planet_orch
+            .send(OrchestratorToPlanet::ResetPlanetAI)
+            .unwrap();
+        match rx_to_orch.recv_timeout(Duration::from_millis(200)) {
+            Ok(PlanetToOrchestrator::ResetPlanetAIResult { planet_id }) => {
+                assert_eq!(planet_id, 100);
+            }
+            _ => panic!("Did not receive ResetPlanetAIResult"),
+        }
+
         // 3. Send Asteroid (AI should build rocket using the charged cell)
         tx_to_planet_orch
             .send(OrchestratorToPlanet::Asteroid(Asteroid::new()))
@@ -1089,10 +2676,13 @@ mod tests {
         // 4. Expect Survival (Ack with Some(Rocket))
         match rx_to_orch.recv_timeout(Duration::from_millis(200)) {
             Ok(PlanetToOrchestrator::AsteroidAck {
-                planet_id, rocket, ..
+                planet_id,
+                rocket,
+                outcome,
             }) => {
                 assert_eq!(planet_id, 100);
                 assert!(rocket.is_some(), "Planet failed to build rocket!");
+                assert_eq!(outcome, AsteroidOutcome::Deflected);
             }
             Ok(_) => panic!("Wrong message type"),
             Err(e) => panic!("Timeout waiting for AsteroidAck: {e}"),
@@ -1112,7 +2702,9 @@ mod tests {
             .send(OrchestratorToPlanet::InternalStateRequest)
             .unwrap();
         match rx_to_orch.recv_timeout(Duration::from_millis(200)) {
-            Ok(PlanetToOrchestrator::Stopped { .. }) => {}
+            Ok(PlanetToOrchestrator::Stopped { reason, .. }) => {
+                assert_eq!(reason, StopReason::ManualStop);
+            }
             _ => panic!("Planet sent incorrect response"),
         }
 
@@ -1209,7 +2801,7 @@ mod tests {
         let handle = thread::spawn(move || {
             let res = planet.run();
             match res {
-                Ok(()) => {}
+                Ok(_) => {}
                 Err(err) => {
                     dbg!(err);
                 }
@@ -1272,7 +2864,9 @@ mod tests {
             .send(ExplorerToPlanet::AvailableEnergyCellRequest { explorer_id })
             .unwrap();
         match expl_dedicated_rx.recv_timeout(Duration::from_millis(200)) {
-            Ok(PlanetToExplorer::Stopped) => {}
+            Ok(PlanetToExplorer::Stopped { reason }) => {
+                assert_eq!(reason, StopReason::ManualStop);
+            }
             _ => panic!("Planet sent incorrect response"),
         }
 
@@ -1314,4 +2908,1473 @@ mod tests {
         drop(orch_tx);
         let _ = handle.join();
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_dummy_state_is_serde() {
+        fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+        assert_serde::<DummyPlanetState>();
+    }
+
+    #[test]
+    fn test_poll_once_without_thread() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _) = get_test_channels();
+
+        let (rx_from_orch, tx_from_planet_orch) = planet_orch_ch;
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let mut planet = Planet::new(
+            7,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            (rx_from_orch, tx_from_planet_orch),
+            rx_from_expl,
+        )
+        .expect("Failed to create planet")
+        .with_running_flag(Arc::clone(&running));
+        assert!(!planet.is_running());
+        assert!(!running.load(Ordering::Acquire));
+
+        // nothing to do yet
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Idle));
+
+        // a stopped planet answers pings
+        orch_tx.send(OrchestratorToPlanet::Ping).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::Pong { planet_id: 7 })
+        ));
+
+        // start the AI
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::StartPlanetAIResult { planet_id: 7 })
+        ));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Idle));
+        assert!(planet.is_running());
+        assert!(running.load(Ordering::Acquire));
+
+        // and so does a running one
+        orch_tx.send(OrchestratorToPlanet::Ping).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::Pong { planet_id: 7 })
+        ));
+
+        // one message is handled per call
+        orch_tx
+            .send(OrchestratorToPlanet::Sunray(Sunray::default()))
+            .unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::Sunray(Sunray::default()))
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(orch_rx.len(), 1);
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(orch_rx.len(), 2);
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Idle));
+
+        // kill the planet
+        orch_tx.send(OrchestratorToPlanet::KillPlanet).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Killed));
+        assert!(!running.load(Ordering::Acquire));
+
+        // orchestrator disconnection is reported
+        drop(orch_tx);
+        assert_eq!(
+            planet.poll_once(),
+            Err(PlanetError::OrchestratorDisconnected)
+        );
+    }
+
+    #[test]
+    fn test_stop_vetoed_by_ai() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _) = get_test_channels();
+
+        let (rx_from_orch, tx_from_planet_orch) = planet_orch_ch;
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let mut ai = MockAI::new();
+        ai.veto_stop = true;
+        let mut planet = Planet::new(
+            3,
+            PlanetType::A,
+            Box::new(ai),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            (rx_from_orch, tx_from_planet_orch),
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx.send(OrchestratorToPlanet::StopPlanetAI).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::StartPlanetAIResult { .. })
+        ));
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::StopDeferred { planet_id: 3 })
+        ));
+        assert!(planet.is_running());
+    }
+
+    #[test]
+    fn test_capabilities_announce_on_start() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _) = get_test_channels();
+
+        let (rx_from_orch, tx_from_planet_orch) = planet_orch_ch;
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let mut planet = Planet::new(
+            4,
+            PlanetType::C,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Carbon],
+            vec![ComplexResourceType::Diamond],
+            (rx_from_orch, tx_from_planet_orch),
+            rx_from_expl,
+        )
+        .expect("Failed to create planet")
+        .with_capabilities_announce(true);
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::StartPlanetAIResult { planet_id: 4 })
+        ));
+        match orch_rx.try_recv() {
+            Ok(PlanetToOrchestrator::CapabilitiesAnnounce {
+                planet_id,
+                resources,
+                combinations,
+            }) => {
+                assert_eq!(planet_id, 4);
+                assert_eq!(resources, HashSet::from([BasicResourceType::Carbon]));
+                assert_eq!(combinations, HashSet::from([ComplexResourceType::Diamond]));
+            }
+            other => panic!("Expected CapabilitiesAnnounce, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_explorer_channel_buffering() {
+        let (tx, rx) = unbounded::<PlanetToExplorer>();
+        let mut channel = ExplorerChannel {
+            sender: tx,
+            buffer: VecDeque::new(),
+            capacity: 2,
+        };
+
+        // held until the buffer is full
+        let stopped = || PlanetToExplorer::Stopped {
+            reason: StopReason::ManualStop,
+        };
+        assert!(channel.send(stopped()).is_ok());
+        assert!(rx.is_empty());
+        assert!(channel.send(stopped()).is_ok());
+        assert_eq!(rx.len(), 2);
+
+        // or until explicitly flushed
+        assert!(channel.send(stopped()).is_ok());
+        assert!(channel.flush().is_ok());
+        assert_eq!(rx.len(), 3);
+
+        // a disconnected explorer discards the buffer
+        drop(rx);
+        assert!(channel.send(stopped()).is_ok());
+        assert!(channel.flush().is_err());
+        assert!(channel.buffer.is_empty());
+    }
+
+    #[test]
+    fn test_explorer_deposit() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, expl_planet_ch) = get_test_channels();
+
+        let (rx_from_orch, tx_from_planet_orch) = planet_orch_ch;
+        let (rx_from_expl, tx_to_expl) = planet_expl_ch;
+        let (orch_tx, _orch_rx) = orch_planet_ch;
+        let (expl_tx, expl_rx) = expl_planet_ch;
+
+        let mut planet = Planet::new(
+            4,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            (rx_from_orch, tx_from_planet_orch),
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                explorer_id: 9,
+                new_sender: tx_to_expl,
+            })
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+
+        let resource = GenericResource::fabricate(ResourceType::Basic(BasicResourceType::Carbon));
+        expl_tx
+            .send(ExplorerToPlanet::DepositResourceRequest {
+                explorer_id: 9,
+                resource,
+            })
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+
+        assert!(matches!(
+            expl_rx.try_recv(),
+            Ok(PlanetToExplorer::DepositResourceResponse { res: Ok(()) })
+        ));
+        assert_eq!(planet.state().resources_iter().count(), 1);
+
+        expl_tx
+            .send(ExplorerToPlanet::StoredResourcesRequest { explorer_id: 9 })
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        match expl_rx.try_recv() {
+            Ok(PlanetToExplorer::StoredResourcesResponse { resources }) => {
+                assert_eq!(
+                    resources,
+                    vec![ResourceType::Basic(BasicResourceType::Carbon)]
+                );
+            }
+            other => panic!("Expected StoredResourcesResponse, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_explorer_msg_only_for_present_explorers() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, expl_planet_ch) = get_test_channels();
+
+        let (rx_from_orch, tx_from_planet_orch) = planet_orch_ch;
+        let (rx_from_expl, tx_to_expl) = planet_expl_ch;
+        let (orch_tx, _orch_rx) = orch_planet_ch;
+        let (expl_tx, expl_rx) = expl_planet_ch;
+
+        let mut planet = Planet::new(
+            5,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            (rx_from_orch, tx_from_planet_orch),
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                explorer_id: 9,
+                new_sender: tx_to_expl,
+            })
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert!(planet.state().is_explorer_present(9));
+
+        let deposit = || ExplorerToPlanet::DepositResourceRequest {
+            explorer_id: 9,
+            resource: GenericResource::fabricate(ResourceType::Basic(BasicResourceType::Carbon)),
+        };
+
+        // the explorer disconnects while its request is being handled
+        drop(expl_rx);
+        expl_tx.send(deposit()).unwrap();
+        assert_eq!(
+            planet.poll_once(),
+            Err(PlanetError::ExplorerDisconnected(9))
+        );
+        assert!(!planet.state().is_explorer_present(9));
+        assert_eq!(planet.state().stored_resources_count(), 1);
+
+        // later messages from it never reach the AI
+        expl_tx.send(deposit()).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.state().stored_resources_count(), 1);
+        assert!(planet.state().present_explorers().is_empty());
+    }
+
+    #[test]
+    fn test_initial_charged_cells() {
+        let (planet_orch_ch, planet_expl_ch, _, _) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+
+        let planet = Planet::new(
+            6,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet")
+        .with_initial_charged_cells(2);
+
+        let charged: Vec<bool> = planet
+            .state()
+            .cells_iter()
+            .map(EnergyCell::is_charged)
+            .collect();
+        assert_eq!(charged.iter().filter(|c| **c).count(), 2);
+        assert!(charged[0] && charged[1]);
+        assert!(
+            planet
+                .state()
+                .cells_enumerate()
+                .all(|(i, is_charged)| is_charged == (i < 2))
+        );
+
+        // the count is clamped to the cells capacity
+        let (planet_orch_ch, planet_expl_ch, _, _) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let planet = Planet::new(
+            7,
+            PlanetType::B,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![ComplexResourceType::Water],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet")
+        .with_initial_charged_cells(100);
+        assert!(planet.state().cells_iter().all(EnergyCell::is_charged));
+    }
+
+    #[test]
+    fn test_broadcast_to_planets() {
+        let (to_orch, from_planets) = unbounded::<PlanetToOrchestrator>();
+        let mut planets = HashMap::new();
+        let mut handles = Vec::new();
+
+        for id in [1, 2] {
+            let (orch_tx, rx_from_orch) = unbounded::<OrchestratorToPlanet>();
+            let (_, rx_from_expl) = unbounded::<ExplorerToPlanet>();
+            let mut planet = Planet::new(
+                id,
+                PlanetType::A,
+                Box::new(MockAI::new()),
+                vec![BasicResourceType::Oxygen],
+                vec![],
+                (rx_from_orch, to_orch.clone()),
+                rx_from_expl,
+            )
+            .expect("Failed to create planet");
+            handles.push(thread::spawn(move || planet.run()));
+            planets.insert(id, orch_tx);
+        }
+
+        // a planet that is already gone
+        let (gone_tx, _) = unbounded::<OrchestratorToPlanet>();
+        planets.insert(3, gone_tx);
+
+        let res = broadcast_to_planets(
+            &planets,
+            &from_planets,
+            || OrchestratorToPlanet::Ping,
+            Duration::from_millis(500),
+        );
+        assert_eq!(res.acks.len(), 2);
+        assert!(matches!(
+            res.acks.get(&1),
+            Some(PlanetToOrchestrator::Pong { planet_id: 1 })
+        ));
+        assert_eq!(res.unreachable, HashSet::from([3]));
+        assert!(res.timed_out.is_empty());
+        assert!(res.unrelated.is_empty());
+
+        // never started planets answer with `Stopped`
+        planets.remove(&3);
+        let res = broadcast_to_planets(
+            &planets,
+            &from_planets,
+            || OrchestratorToPlanet::InternalStateRequest,
+            Duration::from_millis(500),
+        );
+        assert!(
+            res.acks
+                .values()
+                .all(|ack| matches!(ack, PlanetToOrchestrator::Stopped { .. }))
+        );
+
+        let res = broadcast_to_planets(
+            &planets,
+            &from_planets,
+            || OrchestratorToPlanet::KillPlanet,
+            Duration::from_millis(500),
+        );
+        assert_eq!(res.acks.len(), 2);
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), Ok(RunOutcome::Killed));
+        }
+    }
+
+    #[test]
+    fn test_supported_resources_responses() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, expl_planet_ch) = get_test_channels();
+
+        let (rx_from_orch, tx_from_planet_orch) = planet_orch_ch;
+        let (rx_from_expl, tx_to_expl) = planet_expl_ch;
+        let (orch_tx, _orch_rx) = orch_planet_ch;
+        let (expl_tx, expl_rx) = expl_planet_ch;
+
+        let mut planet = Planet::new(
+            8,
+            PlanetType::C,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Hydrogen],
+            vec![ComplexResourceType::Water, ComplexResourceType::Life],
+            (rx_from_orch, tx_from_planet_orch),
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                explorer_id: 9,
+                new_sender: tx_to_expl,
+            })
+            .unwrap();
+        expl_tx
+            .send(ExplorerToPlanet::SupportedResourceRequest { explorer_id: 9 })
+            .unwrap();
+        expl_tx
+            .send(ExplorerToPlanet::SupportedCombinationRequest { explorer_id: 9 })
+            .unwrap();
+        for _ in 0..4 {
+            assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        }
+
+        match expl_rx.try_recv() {
+            Ok(PlanetToExplorer::SupportedResourceResponse { resource_list }) => {
+                assert_eq!(resource_list, HashSet::from([BasicResourceType::Hydrogen]));
+            }
+            other => panic!("Expected SupportedResourceResponse, got {other:?}"),
+        }
+        match expl_rx.try_recv() {
+            Ok(PlanetToExplorer::SupportedCombinationResponse { combination_list }) => {
+                assert_eq!(
+                    combination_list,
+                    HashSet::from([ComplexResourceType::Water, ComplexResourceType::Life])
+                );
+            }
+            other => panic!("Expected SupportedCombinationResponse, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_replace_ai() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, _orch_rx) = orch_planet_ch;
+
+        let first = MockAI::new();
+        let first_stopped = Arc::clone(&first.stop_called);
+        let mut planet = Planet::new(
+            9,
+            PlanetType::A,
+            Box::new(first),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        // a stopped planet just swaps the AIs
+        let second = MockAI::new();
+        let second_started = Arc::clone(&second.start_called);
+        let second_stopped = Arc::clone(&second.stop_called);
+        let _ = planet.replace_ai(Box::new(second));
+        assert!(!first_stopped.load(Ordering::SeqCst));
+        assert!(!second_started.load(Ordering::SeqCst));
+
+        // a running one stops the old AI and starts the new one
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert!(second_started.load(Ordering::SeqCst));
+
+        let third = MockAI::new();
+        let third_started = Arc::clone(&third.start_called);
+        let _ = planet.replace_ai(Box::new(third));
+        assert!(second_stopped.load(Ordering::SeqCst));
+        assert!(third_started.load(Ordering::SeqCst));
+        assert!(planet.is_running());
+    }
+
+    #[test]
+    fn test_explorer_priority() {
+        for explorer_priority in [false, true] {
+            let (planet_orch_ch, planet_expl_ch, orch_planet_ch, expl_planet_ch) =
+                get_test_channels();
+
+            let (rx_from_orch, tx_from_planet_orch) = planet_orch_ch;
+            let (rx_from_expl, tx_to_expl) = planet_expl_ch;
+            let (orch_tx, _orch_rx) = orch_planet_ch;
+            let (expl_tx, expl_rx) = expl_planet_ch;
+
+            let mut planet = Planet::new(
+                10,
+                PlanetType::A,
+                Box::new(MockAI::new()),
+                vec![BasicResourceType::Oxygen],
+                vec![],
+                (rx_from_orch, tx_from_planet_orch),
+                rx_from_expl,
+            )
+            .expect("Failed to create planet")
+            .with_explorer_priority(explorer_priority);
+
+            orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+            orch_tx
+                .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                    explorer_id: 9,
+                    new_sender: tx_to_expl,
+                })
+                .unwrap();
+            assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+            assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+
+            // interleaved traffic: the explorer request is sent between two sunrays
+            orch_tx
+                .send(OrchestratorToPlanet::Sunray(Sunray::default()))
+                .unwrap();
+            expl_tx
+                .send(ExplorerToPlanet::AvailableEnergyCellRequest { explorer_id: 9 })
+                .unwrap();
+            orch_tx
+                .send(OrchestratorToPlanet::Sunray(Sunray::default()))
+                .unwrap();
+
+            let mut polls = 0;
+            while expl_rx.is_empty() {
+                assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+                polls += 1;
+            }
+            assert_eq!(polls, if explorer_priority { 1 } else { 3 });
+        }
+    }
+
+    #[test]
+    fn test_disarm_planet() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _) = get_test_channels();
+
+        let (rx_from_orch, tx_from_planet_orch) = planet_orch_ch;
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let mut planet = Planet::new(
+            11,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            (rx_from_orch, tx_from_planet_orch),
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        // only served while running
+        orch_tx.send(OrchestratorToPlanet::DisarmPlanet).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::Stopped { planet_id: 11, .. })
+        ));
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx.send(OrchestratorToPlanet::DisarmPlanet).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::StartPlanetAIResult { .. })
+        ));
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::DisarmResult {
+                planet_id: 11,
+                rocket: None
+            })
+        ));
+    }
+
+    #[test]
+    fn test_drain_orchestrator() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let mut planet = Planet::new(
+            12,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        assert!(planet.drain_orchestrator().is_empty());
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::InternalStateRequest)
+            .unwrap();
+        let msgs = planet.drain_orchestrator();
+        assert_eq!(msgs.len(), 2);
+        // nothing is handled until the messages are processed
+        assert!(orch_rx.try_recv().is_err());
+
+        for msg in msgs {
+            assert_eq!(
+                planet.process_orchestrator_msg(msg),
+                Ok(PollOutcome::Processed)
+            );
+        }
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::StartPlanetAIResult { .. })
+        ));
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::InternalStateResponse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_default_planet_ai() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, expl_planet_ch) = get_test_channels();
+        let (rx_from_expl, tx_to_expl) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+        let (expl_tx, expl_rx) = expl_planet_ch;
+
+        let mut planet = Planet::new(
+            13,
+            PlanetType::A,
+            Box::new(DefaultPlanetAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::Sunray(Sunray::new()))
+            .unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::Sunray(Sunray::new()))
+            .unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::Asteroid(Asteroid::new()))
+            .unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                explorer_id: 3,
+                new_sender: tx_to_expl,
+            })
+            .unwrap();
+        for _ in 0..5 {
+            assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        }
+
+        // one cell has been used for the rocket, the other one is still charged
+        let acks: Vec<_> = orch_rx.try_iter().collect();
+        assert!(acks.iter().any(|msg| matches!(
+            msg,
+            PlanetToOrchestrator::AsteroidAck {
+                rocket: Some(_),
+                ..
+            }
+        )));
+        assert_eq!(planet.state().charged_cells_count(), 1);
+
+        expl_tx
+            .send(ExplorerToPlanet::GenerateResourceRequest {
+                explorer_id: 3,
+                resource: BasicResourceType::Oxygen,
+            })
+            .unwrap();
+        expl_tx
+            .send(ExplorerToPlanet::AvailableEnergyCellRequest { explorer_id: 3 })
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert!(matches!(
+            expl_rx.try_recv(),
+            Ok(PlanetToExplorer::GenerateResourceResponse {
+                resource: Some(BasicResource::Oxygen(_))
+            })
+        ));
+        assert!(matches!(
+            expl_rx.try_recv(),
+            Ok(PlanetToExplorer::AvailableEnergyCellResponse { available_cells: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_orchestrator_backpressure() {
+        let (_, planet_expl_ch, _, _) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, rx_from_orch) = unbounded::<OrchestratorToPlanet>();
+        let (tx_to_orch, orch_rx) = crossbeam_channel::bounded::<PlanetToOrchestrator>(1);
+
+        let mut planet = Planet::new(
+            14,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            (rx_from_orch, tx_to_orch),
+            rx_from_expl,
+        )
+        .expect("Failed to create planet")
+        .with_orchestrator_send_timeout(Duration::from_millis(10));
+
+        // the first ack fills the channel, the second one can't be sent
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx.send(OrchestratorToPlanet::Ping).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(
+            planet.poll_once(),
+            Err(PlanetError::OrchestratorBackpressure)
+        );
+
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::StartPlanetAIResult { .. })
+        ));
+    }
+
+    #[test]
+    fn test_run_waits_for_a_slow_orchestrator() {
+        let (_, (rx_from_expl, _), _, _) = get_test_channels();
+        let (orch_tx, rx_from_orch) = unbounded::<OrchestratorToPlanet>();
+        let (tx_to_orch, orch_rx) = crossbeam_channel::bounded::<PlanetToOrchestrator>(1);
+
+        let mut planet = Planet::new(
+            29,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            (rx_from_orch, tx_to_orch),
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+        let handle = thread::spawn(move || planet.run());
+
+        // the second ack waits for room on the channel, without ending the planet
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx.send(OrchestratorToPlanet::Ping).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert!(!handle.is_finished());
+
+        assert!(matches!(
+            orch_rx.recv_timeout(Duration::from_millis(200)),
+            Ok(PlanetToOrchestrator::StartPlanetAIResult { planet_id: 29 })
+        ));
+        assert!(matches!(
+            orch_rx.recv_timeout(Duration::from_millis(200)),
+            Ok(PlanetToOrchestrator::Pong { planet_id: 29 })
+        ));
+
+        orch_tx.send(OrchestratorToPlanet::KillPlanet).unwrap();
+        assert!(matches!(
+            orch_rx.recv_timeout(Duration::from_millis(200)),
+            Ok(PlanetToOrchestrator::KillPlanetResult { planet_id: 29 })
+        ));
+        assert_eq!(handle.join().unwrap(), Ok(RunOutcome::Killed));
+    }
+
+    #[test]
+    fn test_max_generation_yield() {
+        let (planet_orch_ch, planet_expl_ch, _, _) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+
+        let planet = Planet::new(
+            15,
+            PlanetType::D,
+            Box::new(MockAI::new()),
+            vec![
+                BasicResourceType::Carbon,
+                BasicResourceType::Oxygen,
+                BasicResourceType::Hydrogen,
+            ],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+        assert!(planet.max_generation_yield().values().all(|n| *n == 0));
+
+        // 4 cells over 3 recipes: the extra one goes to the first type in declaration order
+        let planet = planet.with_initial_charged_cells(4);
+        let first = planet.generator().recipes_sorted()[0];
+        let generation_yield = planet.max_generation_yield();
+        assert_eq!(generation_yield.len(), 3);
+        assert_eq!(generation_yield[&first], 2);
+        assert_eq!(generation_yield.values().sum::<u32>(), 4);
+
+        assert!(planet.state().has_charged_cells(4));
+        assert!(!planet.state().has_charged_cells(5));
+        assert!(planet.can_generate(BasicResourceType::Carbon, 4));
+        assert!(!planet.can_generate(BasicResourceType::Carbon, 5));
+        assert!(!planet.can_generate(BasicResourceType::Silicon, 1));
+    }
+
+    #[test]
+    fn test_run_outcome_on_disconnect() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, _orch_rx) = orch_planet_ch;
+
+        let mut planet = Planet::new(
+            16,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        drop(orch_tx);
+        assert_eq!(planet.run(), Ok(RunOutcome::OrchestratorDisconnected));
+    }
+
+    #[test]
+    fn test_sunray_burst() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let mut planet = Planet::new(
+            17,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet")
+        .with_initial_charged_cells(2);
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::SunrayBurst(
+                (0..5).map(|_| Sunray::new()).collect(),
+            ))
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+
+        // 3 empty cells out of 5
+        assert!(matches!(
+            orch_rx.try_iter().last(),
+            Some(PlanetToOrchestrator::SunrayBurstAck {
+                planet_id: 17,
+                charged: 3,
+                wasted: 2
+            })
+        ));
+        assert_eq!(planet.state().charged_cells_count(), 5);
+    }
+
+    #[test]
+    fn test_pending_explorer_count() {
+        let (planet_orch_ch, planet_expl_ch, _orch_planet_ch, expl_planet_ch) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (expl_tx, _) = expl_planet_ch;
+
+        let mut planet = Planet::new(
+            18,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+        assert_eq!(planet.pending_explorer_count(), 0);
+
+        for _ in 0..2 {
+            expl_tx
+                .send(ExplorerToPlanet::AvailableEnergyCellRequest { explorer_id: 1 })
+                .unwrap();
+        }
+        assert_eq!(planet.pending_explorer_count(), 2);
+
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.pending_explorer_count(), 1);
+    }
+
+    #[test]
+    fn test_max_explorers() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _) = get_test_channels();
+        let (rx_from_expl, tx_to_expl) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let mut planet = Planet::new(
+            19,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet")
+        .with_max_explorers(1);
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        for explorer_id in [1, 2] {
+            orch_tx
+                .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                    explorer_id,
+                    new_sender: tx_to_expl.clone(),
+                })
+                .unwrap();
+        }
+        for _ in 0..3 {
+            assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        }
+
+        let responses: Vec<_> = orch_rx.try_iter().skip(1).collect();
+        assert!(matches!(
+            responses[0],
+            PlanetToOrchestrator::IncomingExplorerResponse {
+                explorer_id: 1,
+                res: Ok(()),
+                ..
+            }
+        ));
+        assert!(matches!(
+            &responses[1],
+            PlanetToOrchestrator::IncomingExplorerResponse {
+                explorer_id: 2,
+                res: Err(_),
+                ..
+            }
+        ));
+        assert!(!planet.state().is_explorer_present(2));
+
+        let dummy = planet.state().to_dummy();
+        assert_eq!(dummy.explorers_count, 1);
+        assert_eq!(dummy.max_explorers, Some(1));
+    }
+
+    #[test]
+    fn test_seconds_since_last_activity() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _expl_planet_ch) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let mut planet = Planet::new(
+            24,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        planet.state.last_activity = Instant::now() - Duration::from_secs(30);
+
+        // monitoring the planet doesn't count as activity
+        orch_tx.send(OrchestratorToPlanet::Ping).unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::InternalStateRequest)
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        match orch_rx.try_iter().last() {
+            Some(PlanetToOrchestrator::InternalStateResponse { planet_state, .. }) => {
+                assert!(planet_state.seconds_since_last_activity >= 30);
+            }
+            other => panic!("Expected InternalStateResponse, got {other:?}"),
+        }
+
+        orch_tx
+            .send(OrchestratorToPlanet::Sunray(Sunray::new()))
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.state().to_dummy().seconds_since_last_activity, 0);
+    }
+
+    #[test]
+    fn test_dummy_state_diff() {
+        let mut state = PlanetState {
+            id: 0,
+            name: None,
+            energy_cells: (0..3).map(|_| EnergyCell::new()).collect(),
+            rocket: None,
+            can_have_rocket: true,
+            resources: Vec::new(),
+            present_explorers: HashSet::new(),
+            max_explorers: None,
+            last_activity: Instant::now(),
+        };
+        state.cell_mut(0).charge(Sunray::new());
+        let before = state.to_dummy();
+        assert!(before.diff(&before).is_empty());
+
+        state.cell_mut(2).charge(Sunray::new());
+        state.build_rocket(0).unwrap();
+        let after = state.to_dummy();
+        assert_eq!(after.cell_levels, vec![(0, 1), (0, 1), (1, 1)]);
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.cells,
+            vec![
+                (0, CellTransition::Discharged),
+                (2, CellTransition::Charged)
+            ]
+        );
+        assert_eq!(diff.rocket_delta, 1);
+        assert_eq!(after.diff(&before).rocket_delta, -1);
+    }
+
+    #[test]
+    fn test_planet_to_explorer_summary() {
+        let oxygen = GenericResource::fabricate(ResourceType::Basic(BasicResourceType::Oxygen));
+        let cases = [
+            (
+                PlanetToExplorer::GenerateResourceResponse {
+                    resource: oxygen.to_oxygen().ok().map(BasicResource::Oxygen),
+                },
+                "GenerateResourceResponse(Oxygen)",
+            ),
+            (
+                PlanetToExplorer::GenerateResourceResponse { resource: None },
+                "GenerateResourceResponse(failed)",
+            ),
+            (
+                PlanetToExplorer::AvailableEnergyCellResponse { available_cells: 3 },
+                "AvailableEnergyCellResponse(3)",
+            ),
+            (
+                PlanetToExplorer::DepositResourceResponse {
+                    res: Err(GenericResource::fabricate(ResourceType::Basic(
+                        BasicResourceType::Carbon,
+                    ))),
+                },
+                "DepositResourceResponse(rejected Carbon)",
+            ),
+            (
+                PlanetToExplorer::Stopped {
+                    reason: StopReason::NeverStarted,
+                },
+                "Stopped(NeverStarted)",
+            ),
+        ];
+
+        for (response, summary) in cases {
+            assert_eq!(response.summary(), summary);
+        }
+    }
+
+    #[test]
+    fn every_request_kind_has_a_matching_response() {
+        for kind in OrchestratorToPlanetKind::all() {
+            let response = kind
+                .expected_response()
+                .unwrap_or_else(|| panic!("{kind} has no response"));
+            assert_eq!(response.responds_to(), Some(*kind), "{kind} -> {response}");
+        }
+        for kind in ExplorerToPlanetKind::all() {
+            let response = kind
+                .expected_response()
+                .unwrap_or_else(|| panic!("{kind} has no response"));
+            assert_eq!(response.responds_to(), Some(*kind), "{kind} -> {response}");
+        }
+
+        let unsolicited: HashSet<_> = PlanetToOrchestratorKind::all()
+            .iter()
+            .filter(|kind| kind.responds_to().is_none())
+            .collect();
+        assert_eq!(
+            unsolicited,
+            HashSet::from([
+                &PlanetToOrchestratorKind::Stopped,
+                &PlanetToOrchestratorKind::CapabilitiesAnnounce,
+                &PlanetToOrchestratorKind::Nack,
+                &PlanetToOrchestratorKind::Destroyed
+            ])
+        );
+        assert!(
+            PlanetToExplorerKind::all()
+                .iter()
+                .filter(|kind| kind.responds_to().is_none())
+                .eq([PlanetToExplorerKind::Stopped].iter())
+        );
+    }
+
+    #[test]
+    fn test_validate_capabilities() {
+        let (planet_orch_ch, planet_expl_ch, _orch_planet_ch, _expl_planet_ch) =
+            get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+
+        let mut planet = Planet::new(
+            20,
+            PlanetType::B,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Hydrogen],
+            vec![ComplexResourceType::Water],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        let errors = planet.validate_capabilities().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Water") && errors[0].contains("Oxygen"));
+
+        // a stored oxygen makes the rule usable
+        planet
+            .state
+            .store_resource(GenericResource::fabricate(ResourceType::Basic(
+                BasicResourceType::Oxygen,
+            )));
+        assert_eq!(planet.validate_capabilities(), Ok(()));
+    }
+
+    #[test]
+    fn test_start_while_running_is_ignored() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _expl_planet_ch) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let mut planet = Planet::new(
+            21,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        for _ in 0..2 {
+            orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+            assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        }
+
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(PlanetToOrchestrator::StartPlanetAIResult { planet_id: 21 })
+        ));
+        assert!(orch_rx.try_recv().is_err());
+        assert!(planet.is_running());
+    }
+
+    #[test]
+    fn test_declined_request_is_nacked() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _expl_planet_ch) = get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+        let (orch_tx, orch_rx) = orch_planet_ch;
+
+        let mut ai = MockAI::new();
+        ai.declined = Some(OrchestratorToPlanetKind::InternalStateRequest);
+        let mut planet = Planet::new(
+            31,
+            PlanetType::A,
+            Box::new(ai),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+        let handle = thread::spawn(move || planet.run());
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        assert!(matches!(
+            orch_rx.recv_timeout(Duration::from_millis(200)),
+            Ok(PlanetToOrchestrator::StartPlanetAIResult { planet_id: 31 })
+        ));
+
+        orch_tx
+            .send(OrchestratorToPlanet::InternalStateRequest)
+            .unwrap();
+        match orch_rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(PlanetToOrchestrator::Nack {
+                planet_id,
+                request_kind,
+                reason,
+            }) => {
+                assert_eq!(planet_id, 31);
+                assert_eq!(request_kind, OrchestratorToPlanetKind::InternalStateRequest);
+                assert_eq!(reason, "busy");
+            }
+            other => panic!("Expected Nack, got {other:?}"),
+        }
+
+        // the other requests are still served
+        orch_tx
+            .send(OrchestratorToPlanet::Sunray(Sunray::new()))
+            .unwrap();
+        assert!(matches!(
+            orch_rx.recv_timeout(Duration::from_millis(200)),
+            Ok(PlanetToOrchestrator::SunrayAck { planet_id: 31 })
+        ));
+
+        orch_tx.send(OrchestratorToPlanet::KillPlanet).unwrap();
+        assert_eq!(handle.join().unwrap(), Ok(RunOutcome::Killed));
+    }
+
+    #[test]
+    fn test_self_destruct() {
+        for self_destruct in [false, true] {
+            let (planet_orch_ch, planet_expl_ch, orch_planet_ch, _expl_planet_ch) =
+                get_test_channels();
+            let (rx_from_expl, _) = planet_expl_ch;
+            let (orch_tx, orch_rx) = orch_planet_ch;
+
+            let mut planet = Planet::new(
+                22,
+                PlanetType::A,
+                Box::new(MockAI::new()),
+                vec![BasicResourceType::Oxygen],
+                vec![],
+                planet_orch_ch,
+                rx_from_expl,
+            )
+            .expect("Failed to create planet")
+            .with_self_destruct(self_destruct);
+
+            orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+            orch_tx
+                .send(OrchestratorToPlanet::Asteroid(Asteroid::new()))
+                .unwrap();
+            assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+
+            // no charged cell: the defense fails
+            let outcome = planet.poll_once();
+            let responses: Vec<_> = orch_rx.try_iter().skip(1).collect();
+            assert!(matches!(
+                responses[0],
+                PlanetToOrchestrator::AsteroidAck {
+                    outcome: AsteroidOutcome::Destroyed,
+                    ..
+                }
+            ));
+
+            if self_destruct {
+                assert_eq!(outcome, Ok(PollOutcome::Killed));
+                assert!(matches!(
+                    responses[1],
+                    PlanetToOrchestrator::Destroyed {
+                        planet_id: 22,
+                        cause: DestroyCause::Asteroid
+                    }
+                ));
+            } else {
+                assert_eq!(outcome, Ok(PollOutcome::Processed));
+                assert_eq!(responses.len(), 1);
+
+                orch_tx.send(OrchestratorToPlanet::KillPlanet).unwrap();
+                assert_eq!(planet.poll_once(), Ok(PollOutcome::Killed));
+                assert!(matches!(
+                    orch_rx.try_iter().collect::<Vec<_>>()[..],
+                    [PlanetToOrchestrator::KillPlanetResult { .. }]
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_planet_name() {
+        let (planet_orch_ch, planet_expl_ch, _orch_planet_ch, _expl_planet_ch) =
+            get_test_channels();
+        let (rx_from_expl, _) = planet_expl_ch;
+
+        let planet = Planet::new(
+            23,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+        assert_eq!(planet.state().name(), None);
+
+        let planet = planet.with_name("Alpha Centauri");
+        assert_eq!(planet.state().name(), Some("Alpha Centauri"));
+        assert_eq!(
+            planet.state().to_dummy().name.as_deref(),
+            Some("Alpha Centauri")
+        );
+    }
+
+    #[test]
+    fn test_remaining_rule_budget() {
+        let (planet_orch_ch, (rx_from_expl, _), _, _) = get_test_channels();
+        let planet = Planet::new(
+            0,
+            PlanetType::A,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+        assert_eq!(planet.remaining_gen_budget(), Some(0));
+        assert_eq!(planet.remaining_comb_budget(), 0);
+
+        let (planet_orch_ch, (rx_from_expl, _), _, _) = get_test_channels();
+        let planet = Planet::new(
+            1,
+            PlanetType::B,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Oxygen, BasicResourceType::Hydrogen],
+            vec![],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+        assert_eq!(planet.remaining_gen_budget(), None);
+        assert_eq!(planet.remaining_comb_budget(), 1);
+    }
+
+    #[test]
+    fn test_reset_planet_ai() {
+        let (planet_orch_ch, (rx_from_expl, _), (orch_tx, orch_rx), _) = get_test_channels();
+
+        let ai = MockAI::new();
+        let reset_called = Arc::clone(&ai.reset_called);
+        let mut planet = Planet::new(
+            28,
+            PlanetType::B,
+            Box::new(ai),
+            vec![BasicResourceType::Hydrogen, BasicResourceType::Oxygen],
+            vec![ComplexResourceType::Water],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::Sunray(Sunray::new()))
+            .unwrap();
+        orch_tx.send(OrchestratorToPlanet::ResetPlanetAI).unwrap();
+        for _ in 0..3 {
+            assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        }
+
+        assert!(matches!(
+            orch_rx.try_iter().last(),
+            Some(PlanetToOrchestrator::ResetPlanetAIResult { planet_id: 28 })
+        ));
+        assert!(reset_called.load(Ordering::SeqCst));
+
+        // the cells and the recipes are kept
+        assert_eq!(planet.state().charged_cells_count(), 1);
+        assert_eq!(
+            planet.generator().all_available_recipes(),
+            HashSet::from([BasicResourceType::Hydrogen, BasicResourceType::Oxygen])
+        );
+        assert_eq!(
+            planet.combinator().all_available_recipes(),
+            HashSet::from([ComplexResourceType::Water])
+        );
+    }
+
+    #[test]
+    fn test_affordable_combinations() {
+        let (planet_orch_ch, planet_expl_ch, orch_planet_ch, expl_planet_ch) = get_test_channels();
+
+        let (rx_from_expl, tx_to_expl) = planet_expl_ch;
+        let (orch_tx, _orch_rx) = orch_planet_ch;
+        let (expl_tx, expl_rx) = expl_planet_ch;
+
+        let mut planet = Planet::new(
+            30,
+            PlanetType::C,
+            Box::new(MockAI::new()),
+            vec![BasicResourceType::Carbon],
+            vec![ComplexResourceType::Diamond],
+            planet_orch_ch,
+            rx_from_expl,
+        )
+        .expect("Failed to create planet");
+
+        orch_tx.send(OrchestratorToPlanet::StartPlanetAI).unwrap();
+        orch_tx
+            .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                explorer_id: 9,
+                new_sender: tx_to_expl,
+            })
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+
+        // no charged cell, nothing can be combined
+        expl_tx
+            .send(ExplorerToPlanet::AffordableCombinationsRequest { explorer_id: 9 })
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert!(matches!(
+            expl_rx.try_recv(),
+            Ok(PlanetToExplorer::AffordableCombinationsResponse { affordable })
+                if affordable.is_empty()
+        ));
+
+        // a single charged cell is enough for any recipe of the planet
+        orch_tx
+            .send(OrchestratorToPlanet::Sunray(Sunray::new()))
+            .unwrap();
+        expl_tx
+            .send(ExplorerToPlanet::AffordableCombinationsRequest { explorer_id: 9 })
+            .unwrap();
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.poll_once(), Ok(PollOutcome::Processed));
+        assert_eq!(planet.state().charged_cells_count(), 1);
+        assert!(matches!(
+            expl_rx.try_recv(),
+            Ok(PlanetToExplorer::AffordableCombinationsResponse { affordable })
+                if affordable == HashSet::from([ComplexResourceType::Diamond])
+        ));
+    }
 }
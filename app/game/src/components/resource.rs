@@ -23,10 +23,11 @@
 //!
 //! Each planet has its own `Generator` and `Combinator`, which are initialized with
 //! the recipes that are available to that planet.
+use crate::components::bag::ResourceBag;
 use crate::components::energy_cell::EnergyCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
 use std::fmt::Display;
-use std::hash::Hash;
 
 /// A trait that provides a common interface for all resources.
 pub trait Resource: Display {
@@ -36,7 +37,9 @@ pub trait Resource: Display {
 
 /// An enum that identifies a resource, which can be either a [`BasicResourceType`] or a
 /// [`ComplexResourceType`], without actually containing the underlying resource.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Basic types are ordered before complex ones, then each follows its own declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ResourceType {
     /// A basic resource type.
     Basic(BasicResourceType),
@@ -44,6 +47,163 @@ pub enum ResourceType {
     Complex(ComplexResourceType),
 }
 
+impl ResourceType {
+    /// Returns the name of the resource type (e.g. `"Water"`), the same returned
+    /// by [`Resource::to_static_str`] for the corresponding resource.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResourceType::Basic(basic) => basic.name(),
+            ResourceType::Complex(complex) => complex.name(),
+        }
+    }
+
+    /// Returns the mass of the resource type (see [`BasicResourceType::mass`]
+    /// and [`ComplexResourceType::mass`]).
+    #[must_use]
+    pub fn mass(&self) -> u32 {
+        match self {
+            ResourceType::Basic(basic) => basic.mass(),
+            ResourceType::Complex(complex) => complex.mass(),
+        }
+    }
+
+    /// Returns the number of charged energy cells needed to make a resource of this type
+    /// from scratch: 1 for a basic resource, see [`ComplexResourceType::cells_needed`]
+    /// for complex ones.
+    #[must_use]
+    pub fn cells_needed(&self) -> usize {
+        match self {
+            ResourceType::Basic(_) => 1,
+            ResourceType::Complex(complex) => complex.cells_needed(),
+        }
+    }
+
+    /// Returns `true` if this is a [`ResourceType::Basic`] type.
+    #[must_use]
+    pub fn is_basic(&self) -> bool {
+        matches!(self, ResourceType::Basic(_))
+    }
+
+    /// Returns `true` if this is a [`ResourceType::Complex`] type.
+    #[must_use]
+    pub fn is_complex(&self) -> bool {
+        matches!(self, ResourceType::Complex(_))
+    }
+
+    /// Returns how a resource of this type is made: basic resources are always generated,
+    /// complex ones are always combined.
+    ///
+    /// Whether a specific planet can make it is told by [`production_mode_for`].
+    #[must_use]
+    pub fn production_mode(&self) -> ProductionMode {
+        match self {
+            ResourceType::Basic(_) => ProductionMode::Generatable,
+            ResourceType::Complex(_) => ProductionMode::Combinable,
+        }
+    }
+}
+
+/// A single step of a [`CraftPlan`], each one consuming one charged energy cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CraftStep {
+    /// Generate a basic resource with a [`Generator`].
+    Generate(BasicResourceType),
+    /// Combine the resources made by the previous steps into a complex resource with a [`Combinator`].
+    Combine(ComplexResourceType),
+}
+
+/// The steps needed to make a complex resource from scratch, in a valid execution order
+/// (see [`ComplexResourceType::craft_plan`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CraftPlan {
+    /// The steps to execute in order: the ingredients of each [`CraftStep::Combine`] are made
+    /// by the steps before it.
+    pub steps: Vec<CraftStep>,
+}
+
+impl CraftPlan {
+    /// Returns the number of charged energy cells needed to execute the whole plan,
+    /// one per step (same as [`ComplexResourceType::cells_needed`]).
+    #[must_use]
+    pub fn cells_needed(&self) -> usize {
+        self.steps.len()
+    }
+}
+
+impl ComplexResourceType {
+    /// Returns the [`CraftPlan`] to make a resource of this type from scratch: each ingredient
+    /// is made (recursively) right before the combination using it, left one first.
+    ///
+    /// Ingredients needed more than once are made once per use, e.g. the plan for `Diamond` is
+    /// `[Generate(Carbon), Generate(Carbon), Combine(Diamond)]`.
+    #[must_use]
+    pub fn craft_plan(&self) -> CraftPlan {
+        fn push_steps(res: ResourceType, steps: &mut Vec<CraftStep>) {
+            match res {
+                ResourceType::Basic(basic) => steps.push(CraftStep::Generate(basic)),
+                ResourceType::Complex(complex) => {
+                    let (lhs, rhs) = complex.request_signature();
+                    push_steps(lhs, steps);
+                    push_steps(rhs, steps);
+                    steps.push(CraftStep::Combine(complex));
+                }
+            }
+        }
+
+        let mut steps = Vec::new();
+        push_steps(ResourceType::Complex(*self), &mut steps);
+        CraftPlan { steps }
+    }
+}
+
+impl ComplexResourceRequest {
+    /// Builds the request to make `target`, taking its two inputs out of `bag`.
+    ///
+    /// Returns [None], leaving the bag untouched, if the bag lacks one of the inputs.
+    pub fn from_bag(target: ComplexResourceType, bag: &mut ResourceBag) -> Option<Self> {
+        let (lhs, rhs) = target.request_signature();
+        let lhs = bag.take(lhs)?;
+        let Some(rhs) = bag.take(rhs) else {
+            bag.insert(lhs);
+            return None;
+        };
+        Self::build(target, lhs, rhs)
+            .map_err(|(lhs, rhs)| {
+                bag.insert(lhs);
+                bag.insert(rhs);
+            })
+            .ok()
+    }
+}
+
+/// How a resource is made (see [`ResourceType::production_mode`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProductionMode {
+    /// Made from scratch by a [`Generator`].
+    Generatable,
+    /// Made out of two other resources by a [`Combinator`].
+    Combinable,
+}
+
+/// Returns how a resource of type `t` is made on a planet with the given [`Generator`] and
+/// [`Combinator`], or [None] if that planet has no recipe for it.
+///
+/// This only checks the recipe of `t` itself: the ingredients of a complex resource may still
+/// have to be brought from other planets (see [`reachable_complex`]).
+#[must_use]
+pub fn production_mode_for(
+    t: ResourceType,
+    generator: &Generator,
+    combinator: &Combinator,
+) -> Option<ProductionMode> {
+    let has_recipe = match t {
+        ResourceType::Basic(basic) => generator.contains(basic),
+        ResourceType::Complex(complex) => combinator.contains(complex),
+    };
+    has_recipe.then(|| t.production_mode())
+}
+
 /// An enum that contains a resource, which can be either a [`BasicResource`] or a
 /// [`ComplexResource`].
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -63,17 +223,140 @@ impl GenericResource {
             GenericResource::ComplexResources(complex) => ResourceType::Complex(complex.get_type()),
         }
     }
+
+    /// Returns the [`ResourceType`] of the `GenericResource`, same as [`GenericResource::get_type`].
+    ///
+    /// Also available on [`BasicResource`] and [`ComplexResource`], to uniformly get a
+    /// [`ResourceType`] out of any resource.
+    #[must_use]
+    pub fn as_resource_type(&self) -> ResourceType {
+        self.get_type()
+    }
+
+    /// Returns `true` if the `GenericResource` is of type `t`.
+    #[must_use]
+    pub fn is_type(&self, t: ResourceType) -> bool {
+        self.get_type() == t
+    }
+
+    /// Returns `true` if the `GenericResource` is a basic resource of type `t`.
+    #[must_use]
+    pub fn is_basic_type(&self, t: BasicResourceType) -> bool {
+        self.is_type(ResourceType::Basic(t))
+    }
+
+    /// Returns `true` if the `GenericResource` is a complex resource of type `t`.
+    #[must_use]
+    pub fn is_complex_type(&self, t: ComplexResourceType) -> bool {
+        self.is_type(ResourceType::Complex(t))
+    }
+}
+
+impl BasicResource {
+    /// Returns the type of this resource wrapped in the unified [`ResourceType`].
+    #[must_use]
+    pub fn as_resource_type(&self) -> ResourceType {
+        ResourceType::Basic(self.get_type())
+    }
+}
+
+impl ComplexResource {
+    /// Returns the type of this resource wrapped in the unified [`ResourceType`].
+    #[must_use]
+    pub fn as_resource_type(&self) -> ResourceType {
+        ResourceType::Complex(self.get_type())
+    }
+}
+
+impl Display for GenericResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenericResource::BasicResources(basic) => basic.fmt(f),
+            GenericResource::ComplexResources(complex) => complex.fmt(f),
+        }
+    }
 }
 
-impl Hash for ComplexResourceType {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        std::mem::discriminant(self).hash(state);
+impl Resource for GenericResource {
+    fn to_static_str(&self) -> &'static str {
+        match self {
+            GenericResource::BasicResources(basic) => basic.to_static_str(),
+            GenericResource::ComplexResources(complex) => complex.to_static_str(),
+        }
     }
 }
 
-impl Hash for BasicResourceType {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        std::mem::discriminant(self).hash(state);
+/// Errors returned by the [`Combinator`] when a complex resource cannot be made.
+///
+/// The input resources are always handed back together with the error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CombineError {
+    /// The combinator has no recipe for the requested complex resource.
+    MissingRecipe(ComplexResourceType),
+    /// The energy cell used to power the combination is not charged.
+    CellNotCharged,
+    /// The given resources are not the ingredients of the requested complex resource.
+    InvalidIngredients(ComplexResourceType),
+}
+
+impl Display for CombineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CombineError::MissingRecipe(complex) => {
+                write!(f, "there isn't a recipe for {complex:?}")
+            }
+            CombineError::CellNotCharged => write!(f, "EnergyCell not charged!"),
+            CombineError::InvalidIngredients(complex) => {
+                write!(
+                    f,
+                    "the given resources are not the ingredients of {complex:?}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for CombineError {}
+
+/// Errors returned by the [`Generator`] when a basic resource cannot be made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+    /// The generator has no recipe for the requested basic resource.
+    MissingRecipe(BasicResourceType),
+    /// The energy cell used to power the generation is not charged.
+    CellNotCharged,
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerateError::MissingRecipe(basic) => write!(f, "there isn't a recipe for {basic:?}"),
+            GenerateError::CellNotCharged => write!(f, "EnergyCell not charged!"),
+        }
+    }
+}
+
+impl Error for GenerateError {}
+
+// private conversions between a concrete resource and a `GenericResource`,
+// handing the resource back when it is of a different type
+trait GenericDowncast: Sized {
+    fn from_generic(res: GenericResource) -> Result<Self, GenericResource>;
+    fn into_generic(self) -> GenericResource;
+}
+
+// downcasts a pair of generic resources to the `(L, R)` concrete types,
+// returning both resources (in the original order) on failure
+fn downcast_pair<L: GenericDowncast, R: GenericDowncast>(
+    a: GenericResource,
+    b: GenericResource,
+) -> Result<(L, R), (GenericResource, GenericResource)> {
+    match L::from_generic(a) {
+        Ok(l) => match R::from_generic(b) {
+            Ok(r) => Ok((l, r)),
+            Err(b) => Err((l.into_generic(), b)),
+        },
+        Err(a) => Err((a, b)),
     }
 }
 
@@ -116,6 +399,18 @@ impl Combinator {
         matches!(&self.set.get(&complex), Some(_f))
     }
 
+    /// Returns the number of recipes in the `Combinator`.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns `true` if the `Combinator` has no recipe.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
     /// # Internal API - Do not use directly
     ///
     /// Adds a recipe for the specified [`ComplexResourceType`] to the `Combinator`.
@@ -131,11 +426,70 @@ impl Combinator {
         }
     }
 
+    /// Removes all the recipes from the `Combinator`.
+    pub fn clear(&mut self) {
+        self.set.clear();
+    }
+
     /// Returns a `HashSet` of all the recipes available in the `Combinator`.
     #[must_use]
     pub fn all_available_recipes(&self) -> HashSet<ComplexResourceType> {
         self.set.iter().copied().collect()
     }
+
+    /// Returns all the recipes available in the `Combinator`, sorted following
+    /// the [`ComplexResourceType`] declaration order (e.g. for a stable display order).
+    #[must_use]
+    pub fn recipes_sorted(&self) -> Vec<ComplexResourceType> {
+        let mut recipes: Vec<_> = self.set.iter().copied().collect();
+        recipes.sort_unstable();
+        recipes
+    }
+
+    /// Same as [`Combinator::try_make`], but on failure the two input resources are put back
+    /// into `bag` instead of being returned with the error.
+    ///
+    /// # Errors
+    /// Returns the [`CombineError`] telling why the resource could not be made.
+    pub fn try_make_into(
+        &self,
+        req: ComplexResourceRequest,
+        energy_cell: &mut EnergyCell,
+        bag: &mut ResourceBag,
+    ) -> Result<ComplexResource, CombineError> {
+        self.try_make(req, energy_cell).map_err(|(err, lhs, rhs)| {
+            bag.insert(lhs);
+            bag.insert(rhs);
+            err
+        })
+    }
+
+    /// Returns the recipes of the `Combinator` whose two ingredients are both
+    /// [`BasicResourceType`]s the given [`Generator`] can make, i.e. the complex resources a
+    /// planet can make in a single step from scratch.
+    ///
+    /// Unlike [`reachable_complex`], this doesn't follow recipes needing other complex resources.
+    #[must_use]
+    pub fn self_serviceable(&self, generator: &Generator) -> HashSet<ComplexResourceType> {
+        let generated = |res: &ResourceType| matches!(res, ResourceType::Basic(basic) if generator.contains(*basic));
+        recipe_graph()
+            .into_iter()
+            .filter(|(result, lhs, rhs)| self.contains(*result) && generated(lhs) && generated(rhs))
+            .map(|(result, _, _)| result)
+            .collect()
+    }
+
+    /// Returns the recipes of the `Combinator`, each mapped to its two ingredients
+    /// (as given by [`ComplexResourceType::request_signature`]).
+    ///
+    /// Useful to export the full configuration of a planet: what it can make, and from what.
+    #[must_use]
+    pub fn recipe_table(&self) -> HashMap<ComplexResourceType, (ResourceType, ResourceType)> {
+        self.all_available_recipes()
+            .into_iter()
+            .map(|result| (result, result.request_signature()))
+            .collect()
+    }
 }
 
 /// Manages the recipes and production of basic resources for a planet.
@@ -176,6 +530,18 @@ impl Generator {
         matches!(&self.set.get(&basic), Some(_f))
     }
 
+    /// Returns the number of recipes in the `Generator`.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns `true` if the `Generator` has no recipe.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
     /// # Internal API - Do not use directly
     ///
     /// Adds a recipe for the specified [`BasicResourceType`] to the `Generator`.
@@ -191,11 +557,85 @@ impl Generator {
         }
     }
 
+    /// Removes all the recipes from the `Generator`.
+    pub fn clear(&mut self) {
+        self.set.clear();
+    }
+
     /// Returns a `HashSet` of all the recipes available in the `Generator`.
     #[must_use]
     pub fn all_available_recipes(&self) -> HashSet<BasicResourceType> {
         self.set.iter().copied().collect()
     }
+
+    /// Returns all the recipes available in the `Generator`, sorted following
+    /// the [`BasicResourceType`] declaration order (e.g. for a stable display order).
+    #[must_use]
+    pub fn recipes_sorted(&self) -> Vec<BasicResourceType> {
+        let mut recipes: Vec<_> = self.set.iter().copied().collect();
+        recipes.sort_unstable();
+        recipes
+    }
+}
+
+/// Returns every [`ComplexResourceType`] that can eventually be made on a planet with the given
+/// [`Generator`] and [`Combinator`], starting only from the basic resources it can generate.
+///
+/// A complex resource is reachable if the combinator has its recipe and both its ingredients are
+/// reachable themselves (see [`recipe_graph`]), e.g. `Life` needs `Water` (thus `Hydrogen`
+/// and `Oxygen`) and `Carbon`.
+#[must_use]
+pub fn reachable_complex(
+    generator: &Generator,
+    combinator: &Combinator,
+) -> HashSet<ComplexResourceType> {
+    let seed = generator
+        .all_available_recipes()
+        .into_iter()
+        .map(ResourceType::Basic)
+        .collect();
+
+    reachable_from(seed, combinator)
+        .into_iter()
+        .filter_map(|res| match res {
+            ResourceType::Complex(complex) => Some(complex),
+            ResourceType::Basic(_) => None,
+        })
+        .collect()
+}
+
+// the resources in `seed` plus every complex resource the combinator can make out of them,
+// directly or through other complex resources made along the way
+pub(crate) fn reachable_from(
+    mut seed: HashSet<ResourceType>,
+    combinator: &Combinator,
+) -> HashSet<ResourceType> {
+    // expand until no new resource can be made
+    let recipes = recipe_graph();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (result, lhs, rhs) in &recipes {
+            if combinator.contains(*result)
+                && seed.contains(lhs)
+                && seed.contains(rhs)
+                && seed.insert(ResourceType::Complex(*result))
+            {
+                changed = true;
+            }
+        }
+    }
+    seed
+}
+
+// expands to the mass given in a `define_resources!` invocation, if any
+macro_rules! resource_mass {
+    () => {
+        None
+    };
+    ($mass:literal) => {
+        Some($mass)
+    };
 }
 
 /// A macro for defining the basic and complex resources.
@@ -206,8 +646,10 @@ impl Generator {
 ///
 /// ## Arguments
 ///
-/// * `Basic`: A list of the basic resources to define.
-/// * `Complex`: A list of the complex resources to define.
+/// * `Basic`: A list of the basic resources to define. Each one can be followed by `= mass`
+///   to override its default mass of 1.
+/// * `Complex`: A list of the complex resources to define. Each one can be followed by `= mass`
+///   to override its default mass, which is the sum of the masses of its ingredients.
 ///
 /// ## Generated Code
 ///
@@ -226,7 +668,10 @@ impl Generator {
 ///   the resources.
 ///
 macro_rules! define_resources {
-        (Basic: [$($basic:ident),* $(,)?], Complex: [$($complex:ident),* $(,)?]) => {
+        (
+            Basic: [$($basic:ident $(= $basic_mass:literal)?),* $(,)?],
+            Complex: [$($complex:ident $(= $complex_mass:literal)?),* $(,)?]
+        ) => {
 
             $(
                 /// A basic resource.
@@ -242,11 +687,12 @@ macro_rules! define_resources {
                 }
 
                 impl $basic {
+                    // the resource type, known at compile time
+                    const TYPE: ResourceType = ResourceType::Basic(BasicResourceType::$basic);
+
                     /// Converts this resource to a [`ResourceType`].
                     pub fn to_type(&self) -> ResourceType {
-                        match self {
-                            $basic { .. } =>  ResourceType::Basic(BasicResourceType::$basic),
-                        }
+                        Self::TYPE
                     }
 
                     /// Converts this resource to a [`GenericResource`].
@@ -269,6 +715,19 @@ macro_rules! define_resources {
 
 
 
+                impl GenericDowncast for $basic {
+                    fn from_generic(res: GenericResource) -> Result<Self, GenericResource> {
+                        match res {
+                            GenericResource::BasicResources(BasicResource::$basic(r)) => Ok(r),
+                            other => Err(other),
+                        }
+                    }
+
+                    fn into_generic(self) -> GenericResource {
+                        self.to_generic()
+                    }
+                }
+
                 impl Resource for $basic {
                     fn to_static_str(&self) -> &'static str {
                         stringify!($basic)
@@ -276,8 +735,11 @@ macro_rules! define_resources {
                 }
 
                  paste::paste!{
-                    fn [<generate_ $basic:lower>] (energy_cell: &mut EnergyCell) -> Result<$basic , String> {
-                            energy_cell.discharge().and_then(|()| Ok($basic { _private: () }))
+                    fn [<generate_ $basic:lower>] (energy_cell: &mut EnergyCell) -> Result<$basic , GenerateError> {
+                            energy_cell
+                                .discharge()
+                                .map(|()| $basic { _private: () })
+                                .map_err(|_| GenerateError::CellNotCharged)
                     }
                  }
             )*
@@ -296,6 +758,19 @@ macro_rules! define_resources {
                     }
                 }
 
+                impl GenericDowncast for $complex {
+                    fn from_generic(res: GenericResource) -> Result<Self, GenericResource> {
+                        match res {
+                            GenericResource::ComplexResources(ComplexResource::$complex(r)) => Ok(r),
+                            other => Err(other),
+                        }
+                    }
+
+                    fn into_generic(self) -> GenericResource {
+                        self.to_generic()
+                    }
+                }
+
                 impl Resource for $complex {
                     fn to_static_str(&self) -> &'static str {
                         stringify!($complex)
@@ -303,11 +778,12 @@ macro_rules! define_resources {
                 }
 
                  impl $complex {
+                        // the resource type, known at compile time
+                        const TYPE: ResourceType = ResourceType::Complex(ComplexResourceType::$complex);
+
                         /// Converts this resource to a [`ResourceType`].
                         pub fn to_type(&self) -> ResourceType {
-                            match self {
-                                $complex { .. } =>  ResourceType::Complex(ComplexResourceType::$complex),
-                            }
+                            Self::TYPE
                         }
 
                         /// Converts this resource to a [`GenericResource`].
@@ -383,6 +859,20 @@ macro_rules! define_resources {
 
             impl BasicResourceType{
 
+                    /// Returns the name of the resource type (e.g. `"Oxygen"`).
+                    pub fn name(&self) -> &'static str {
+                        match self {
+                            $( BasicResourceType::$basic => stringify!($basic), )*
+                        }
+                    }
+
+                    /// Returns the mass of the resource type, e.g. to enforce bag capacity limits.
+                    pub fn mass(&self) -> u32 {
+                        match self {
+                            $( BasicResourceType::$basic => resource_mass!($($basic_mass)?).unwrap_or(1), )*
+                        }
+                    }
+
                     paste::paste! {
                         $(
                             /// Returns `true` if the resource type is `$basic`.
@@ -401,6 +891,20 @@ macro_rules! define_resources {
 
                impl ComplexResourceType{
 
+                    /// Returns the name of the resource type (e.g. `"Water"`).
+                    pub fn name(&self) -> &'static str {
+                        match self {
+                            $( ComplexResourceType::$complex => stringify!($complex), )*
+                        }
+                    }
+
+                    // the mass set in the macro invocation, overriding the recipe-based one
+                    fn mass_override(self) -> Option<u32> {
+                        match self {
+                            $( ComplexResourceType::$complex => resource_mass!($($complex_mass)?), )*
+                        }
+                    }
+
                     paste::paste! {
                         $(
                             /// Returns `true` if the resource type is `$complex`.
@@ -419,7 +923,9 @@ macro_rules! define_resources {
             /// An enum that identifies a [`ComplexResource`] type without actually containing the
             /// underlying resource.
             ///
-            #[derive(Debug,Clone,Copy, Eq)]
+            /// Variants are ordered following their declaration order in the `define_resources!`
+            /// macro invocation.
+            #[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,PartialOrd,Ord)]
             pub enum ComplexResourceType {
                 $(
                     $complex,
@@ -452,6 +958,21 @@ macro_rules! define_resources {
                 }
             }
             impl GenericResource {
+                /// # Test utilities - Do not use in production code
+                ///
+                /// Fabricates a resource of the given [`ResourceType`] out of nothing, without
+                /// a [`Generator`] or a [`Combinator`] and without consuming any energy.
+                ///
+                /// Only available with the `test-utils` feature, to populate bags and messages in tests.
+                #[cfg(any(test, feature = "test-utils"))]
+                #[must_use]
+                pub fn fabricate(t: ResourceType) -> GenericResource {
+                    match t {
+                        $( ResourceType::Basic(BasicResourceType::$basic) => $basic { _private: () }.to_generic(), )*
+                        $( ResourceType::Complex(ComplexResourceType::$complex) => $complex { _private: () }.to_generic(), )*
+                    }
+                }
+
                 paste::paste! {
                    $(
                         /// Attempts to convert the `GenericResource` into a `$complex`.
@@ -517,20 +1038,24 @@ macro_rules! define_resources {
             }
 
 
-            impl PartialEq<Self> for ComplexResourceType {
-                fn eq(&self, other: &Self) -> bool {
-                    match (self, other) {
-                        $( ( ComplexResourceType::$complex ,  ComplexResourceType::$complex) => { true}, )*
-                        (_, _) => { false}
-                    }
-                }
+            /// Returns every [`BasicResourceType`] that can be generated, in declaration order.
+            ///
+            /// Together with [`recipe_graph`], it describes the whole statically-known recipe structure.
+            #[must_use]
+            pub fn generation_rules() -> Vec<BasicResourceType> {
+                vec![ $( BasicResourceType::$basic, )* ]
             }
-             impl PartialEq<Self> for BasicResourceType {
-                fn eq(&self, other: &Self) -> bool {
-                    match (self, other) {
-                        $( ( BasicResourceType::$basic ,  BasicResourceType::$basic) => { true}, )*
-                        (_, _) => { false}
-                    }
+
+            /// Returns the [`ResourceType`] whose name (as given by [`ResourceType::name`]) is `s`,
+            /// be it basic or complex, or [None] if no resource has that name.
+            ///
+            /// This is the inverse of [`Resource::to_static_str`]: the match is exact (case-sensitive).
+            #[must_use]
+            pub fn resource_type_from_str(s: &str) -> Option<ResourceType> {
+                match s {
+                    $( stringify!($basic) => Some(ResourceType::Basic(BasicResourceType::$basic)), )*
+                    $( stringify!($complex) => Some(ResourceType::Complex(ComplexResourceType::$complex)), )*
+                    _ => None,
                 }
             }
 
@@ -558,13 +1083,48 @@ macro_rules! define_resources {
                 )*
             }
 
+            impl Display for BasicResource {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    match self {
+                        $( BasicResource::$basic(res) => res.fmt(f), )*
+                    }
+                }
+            }
+
+            impl Display for ComplexResource {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    match self {
+                        $( ComplexResource::$complex(res) => res.fmt(f), )*
+                    }
+                }
+            }
+
+            impl Resource for BasicResource {
+                fn to_static_str(&self) -> &'static str {
+                    match self {
+                        $( BasicResource::$basic(res) => res.to_static_str(), )*
+                    }
+                }
+            }
+
+            impl Resource for ComplexResource {
+                fn to_static_str(&self) -> &'static str {
+                    match self {
+                        $( ComplexResource::$complex(res) => res.to_static_str(), )*
+                    }
+                }
+            }
+
             /// An enum that identifies a [`BasicResource`] type without actually containing the
             /// underlying resource.
             ///
             /// This enum is generated by the `define_resources!` macro and contains a variant for
             /// each basic resource defined in the macro invocation. It is primarily used for
             /// type identification and recipe definitions within the [`Generator`].
-            #[derive(Debug,Clone,Copy,Eq)]
+            ///
+            /// Variants are ordered following their declaration order in the `define_resources!`
+            /// macro invocation.
+            #[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,PartialOrd,Ord)]
             pub enum BasicResourceType {
                 $(
                     $basic,
@@ -593,12 +1153,12 @@ macro_rules! define_resources {
                          /// # Errors
                          ///
                          /// Returns an error if there is no recipe for this resource or if the `energy_cell` is not charged.
-                         pub fn [<make_ $basic:lower>]  (&self, energy_cell : &mut EnergyCell ) -> Result<$basic, String > {
+                         pub fn [<make_ $basic:lower>]  (&self, energy_cell : &mut EnergyCell ) -> Result<$basic, GenerateError > {
                              let b = BasicResourceType::$basic;
                             if let Some(_f_enum)  =  &self.set.get(&b) {
-                                Ok( [<generate_ $basic:lower>] (energy_cell )?)
+                                [<generate_ $basic:lower>] (energy_cell )
                             } else {
-                               Err(format!("there isn't a recipe for {:?}", b))
+                               Err(GenerateError::MissingRecipe(b))
                             }
                         }
                     )*
@@ -625,19 +1185,19 @@ macro_rules! define_resources {
                   ///
                   /// Returns an error if the `energy_cell` is not charged or if there is no recipe
                   /// for the requested resource type.
-                  pub fn try_make(&self , req :  BasicResourceType , energy_cell: &mut EnergyCell) -> Result<BasicResource, String> {
+                  pub fn try_make(&self , req :  BasicResourceType , energy_cell: &mut EnergyCell) -> Result<BasicResource, GenerateError> {
                     if !energy_cell.is_charged() {
-                        return Err("The energy is not charged".to_string());
+                        return Err(GenerateError::CellNotCharged);
                     }
                     match req {
                         $(
                             BasicResourceType::$basic => {
                             if self.set.contains( &BasicResourceType::$basic ) {
-                                energy_cell.discharge()?;
+                                energy_cell.discharge().map_err(|_| GenerateError::CellNotCharged)?;
                                 Ok($basic{ _private: () }.to_basic())
                             }
                             else {
-                                Err(format!("Missing recipe for {:?}", stringify!($basic) ))
+                                Err(GenerateError::MissingRecipe(BasicResourceType::$basic))
                             }
                         },
                         )*
@@ -672,10 +1232,10 @@ macro_rules! define_combination_rules {
         ($($result:ident from  $lhs:ident + $rhs:ident ),* $(,)?) => {
             $(
                 paste::paste! {
-                    fn [<  $result:lower _fn >] ( r1: $lhs  , r2: $rhs , energy_cell: &mut EnergyCell) ->  Result<$result, (String ,$lhs , $rhs ) >    {
+                    fn [<  $result:lower _fn >] ( r1: $lhs  , r2: $rhs , energy_cell: &mut EnergyCell) ->  Result<$result, (CombineError ,$lhs , $rhs ) >    {
                         match energy_cell.discharge(){
-                            Ok(_) => Ok($result { _private: () }),
-                            Err(e) => Err( (e, r1, r2 )),
+                            Ok(()) => Ok($result { _private: () }),
+                            Err(_) => Err( (CombineError::CellNotCharged, r1, r2 )),
                         }
                    }
                 }
@@ -689,6 +1249,10 @@ macro_rules! define_combination_rules {
                 /// to produce the target complex resource.
                 ///
                 /// It allows passing all ingredients for a reaction as a single object to the [`Combinator`].
+                ///
+                /// The ingredients must be given in the order in which they are declared by the recipe
+                /// (e.g. `Water(Hydrogen, Oxygen)`); use [`Combinator::try_make_generic`] to combine
+                /// resources regardless of their order.
                 #[derive(Debug, PartialEq,Eq,Hash )]
                 pub enum ComplexResourceRequest{
                      $(
@@ -697,6 +1261,104 @@ macro_rules! define_combination_rules {
                 }
             }
 
+            /// Returns the crafting graph of all the combination rules, in declaration order.
+            ///
+            /// Each entry is a `(result, lhs, rhs)` triple: the complex resource type that is produced
+            /// and the types of the two resources it is made from.
+            #[must_use]
+            pub fn recipe_graph() -> Vec<(ComplexResourceType, ResourceType, ResourceType)> {
+                vec![ $( (ComplexResourceType::$result, $lhs::TYPE, $rhs::TYPE), )* ]
+            }
+
+            impl ComplexResourceType {
+                /// Returns the mass of the resource type, e.g. to enforce bag capacity limits.
+                ///
+                /// Unless overridden, it is the sum of the masses of the recipe ingredients.
+                pub fn mass(&self) -> u32 {
+                    self.mass_override().unwrap_or_else(|| match self {
+                        $( ComplexResourceType::$result => $lhs::TYPE.mass() + $rhs::TYPE.mass(), )*
+                    })
+                }
+
+                /// Returns the number of charged energy cells needed to make a resource of this
+                /// type from scratch: one per generation and one per combination in its recipe tree
+                /// (e.g. 3 for `Water`: `Hydrogen`, `Oxygen` and the combination itself).
+                #[must_use]
+                pub fn cells_needed(&self) -> usize {
+                    match self {
+                        $( ComplexResourceType::$result => 1 + $lhs::TYPE.cells_needed() + $rhs::TYPE.cells_needed(), )*
+                    }
+                }
+
+                /// Returns the types of the two inputs of the recipe, in the order in which they
+                /// must be given to the corresponding [`ComplexResourceRequest`] variant
+                /// (e.g. `(Hydrogen, Oxygen)` for `Water`).
+                #[must_use]
+                pub fn request_signature(&self) -> (ResourceType, ResourceType) {
+                    match self {
+                        $( ComplexResourceType::$result => ($lhs::TYPE, $rhs::TYPE), )*
+                    }
+                }
+
+                /// Returns a human-readable description of the recipe, like `"Robot = Silicon + Life"`.
+                #[must_use]
+                pub fn describe(&self) -> String {
+                    let (lhs, rhs) = self.request_signature();
+                    format!("{} = {} + {}", self.name(), lhs.name(), rhs.name())
+                }
+            }
+
+            impl ComplexResourceRequest {
+                /// Checks at runtime that the carried inputs match the types expected by the
+                /// recipe (see [`ComplexResourceType::request_signature`]), e.g. when the request
+                /// comes from a deserialized message.
+                ///
+                /// Requests built in-process are type-safe, so the check always succeeds for them.
+                ///
+                /// # Errors
+                /// Returns [`CombineError::InvalidIngredients`] if the inputs don't match the recipe.
+                pub fn validate(&self) -> Result<(), CombineError> {
+                    let (target, inputs) = match self {
+                        $(
+                            ComplexResourceRequest::$result(l, r) => {
+                                (ComplexResourceType::$result, (l.to_type(), r.to_type()))
+                            }
+                        )*
+                    };
+
+                    if inputs == target.request_signature() {
+                        Ok(())
+                    } else {
+                        Err(CombineError::InvalidIngredients(target))
+                    }
+                }
+
+                /// Builds the request to make `target` out of two generic resources, downcasting them
+                /// to the ingredients of the recipe in either order (e.g. `Water` can be built from
+                /// `(Oxygen, Hydrogen)` as well as from `(Hydrogen, Oxygen)`).
+                ///
+                /// # Errors
+                /// Returns the two input resources, in the given order, if they are not the
+                /// ingredients of `target`.
+                pub fn build(
+                    target: ComplexResourceType,
+                    a: GenericResource,
+                    b: GenericResource,
+                ) -> Result<Self, (GenericResource, GenericResource)> {
+                    match target {
+                        $(
+                            ComplexResourceType::$result => {
+                                downcast_pair::<$lhs, $rhs>(a, b)
+                                    .or_else(|(a, b)| {
+                                        downcast_pair::<$lhs, $rhs>(b, a).map_err(|(b, a)| (a, b))
+                                    })
+                                    .map(|(l, r)| ComplexResourceRequest::$result(l, r))
+                            }
+                        )*
+                    }
+                }
+            }
+
             impl Combinator {
                 paste::paste! {
                     $(
@@ -721,12 +1383,12 @@ macro_rules! define_combination_rules {
                          /// # Errors
                          ///
                          /// Returns an error if there is no recipe for this resource, if the `energy_cell` is not charged, or if the energy discharge fails. The input resources are returned in the error tuple to prevent ownership loss.
-                         pub fn [<make_ $result:lower>]  (&self, r1 :  $lhs  ,r2 : $rhs , energy_cell: &mut EnergyCell  ) -> Result<$result, (String, $lhs , $rhs )  > {
+                         pub fn [<make_ $result:lower>]  (&self, r1 :  $lhs  ,r2 : $rhs , energy_cell: &mut EnergyCell  ) -> Result<$result, (CombineError, $lhs , $rhs )  > {
                              let c = ComplexResourceType::$result;
                             if let Some(_f_enum)  =  &self.set.get( &c ) {
                                   [<$result:lower _fn >](r1,r2 , energy_cell )
                             } else {
-                               Err((format!("there isn't a recipe for {:?}", c), r1 ,r2 ) )
+                               Err((CombineError::MissingRecipe(c), r1 ,r2 ) )
                             }
                         }
                     )*
@@ -754,7 +1416,7 @@ macro_rules! define_combination_rules {
                  /// Returns an error if there is no recipe for the requested complex resource or if the
                  /// energy cell discharge fails. The input resources are returned in the error tuple to
                  /// prevent ownership loss on failure.
-                 pub fn try_make(&self , req :  ComplexResourceRequest , energy_cell: &mut EnergyCell) -> Result<ComplexResource, (String, GenericResource , GenericResource )> {
+                 pub fn try_make(&self , req :  ComplexResourceRequest , energy_cell: &mut EnergyCell) -> Result<ComplexResource, (CombineError, GenericResource , GenericResource )> {
                     match req {
                         $(
                         ComplexResourceRequest::$result(r1, r2) => {
@@ -768,13 +1430,53 @@ macro_rules! define_combination_rules {
                                     }
                             }
                             else {
-                               Err((format!("there isn't a recipe for {:?}", stringify!($result)), r1.to_generic() ,r2.to_generic() ) )
+                               Err((CombineError::MissingRecipe(ComplexResourceType::$result), r1.to_generic() ,r2.to_generic() ) )
                             }
                         },
                         )*
                     }
                 }
 
+                 /// Attempts to create a complex resource out of two [`GenericResource`]s.
+                 ///
+                 /// The two resources are checked against the ingredients of the `target` recipe,
+                 /// converted to their concrete types and passed to [`Combinator::try_make`].
+                 ///
+                 /// Unlike [`ComplexResourceRequest`], this method is **not** order-sensitive: the inputs
+                 /// are accepted in any order for every recipe, and are rejected only when their types
+                 /// don't match the recipe ingredients. Recipes with two identical ingredients
+                 /// (e.g. `Diamond` from `Carbon + Carbon`) are trivially order-agnostic.
+                 ///
+                 /// # Arguments
+                 ///
+                 /// * `target` - The type of the complex resource to create.
+                 /// * `a` - The first input resource.
+                 /// * `b` - The second input resource.
+                 /// * `energy_cell` - A mutable reference to an `EnergyCell` which will be
+                 ///   discharged during resource creation.
+                 ///
+                 /// # Returns
+                 ///
+                 /// A `Result` indicating success:
+                 /// * `Ok(ComplexResource)`: The complex resource was successfully created.
+                 ///
+                 /// # Errors
+                 ///
+                 /// Returns an error if the inputs are not the ingredients of `target`, if there is
+                 /// no recipe for it or if the energy cell discharge fails. The input resources are
+                 /// returned in the error tuple to prevent ownership loss on failure.
+                 pub fn try_make_generic(
+                     &self,
+                     target: ComplexResourceType,
+                     a: GenericResource,
+                     b: GenericResource,
+                     energy_cell: &mut EnergyCell,
+                 ) -> Result<ComplexResource, (CombineError, GenericResource, GenericResource)> {
+                     let req = ComplexResourceRequest::build(target, a, b)
+                         .map_err(|(a, b)| (CombineError::InvalidIngredients(target), a, b))?;
+                     self.try_make(req, energy_cell)
+                 }
+
             }
 
         };
@@ -838,7 +1540,7 @@ mod tests {
         let result = generator.make_oxygen(&mut cell);
 
         assert!(result.is_err());
-        assert_eq!(result.err().unwrap(), "EnergyCell not charged!");
+        assert_eq!(result.err().unwrap(), GenerateError::CellNotCharged);
     }
 
     #[test]
@@ -850,7 +1552,9 @@ mod tests {
         let result = generator.make_oxygen(&mut cell);
 
         assert!(result.is_err());
-        assert!(result.err().unwrap().contains("there isn't a recipe for"));
+        let err = result.err().unwrap();
+        assert_eq!(err, GenerateError::MissingRecipe(BasicResourceType::Oxygen));
+        assert!(err.to_string().contains("there isn't a recipe for"));
     }
 
     #[test]
@@ -1007,14 +1711,15 @@ mod tests {
 
         // Test fail no charge
         let result = generator.try_make(BasicResourceType::Oxygen, &mut cell);
-        assert!(result.is_err());
-        assert_eq!(result.err().unwrap(), "The energy is not charged");
+        assert_eq!(result.err().unwrap(), GenerateError::CellNotCharged);
 
         // Test fail no recipe
         let mut cell = get_charged_cell();
         let result = generator.try_make(BasicResourceType::Hydrogen, &mut cell);
-        assert!(result.is_err());
-        assert!(result.err().unwrap().contains("Missing recipe for"));
+        assert_eq!(
+            result.err().unwrap(),
+            GenerateError::MissingRecipe(BasicResourceType::Hydrogen)
+        );
     }
 
     #[test]
@@ -1047,7 +1752,8 @@ mod tests {
         let result = combinator.try_make(request, &mut cell);
         assert!(result.is_err());
         let (err, _, _) = result.err().unwrap();
-        assert_eq!(err, "EnergyCell not charged!");
+        assert_eq!(err, CombineError::CellNotCharged);
+        assert_eq!(err.to_string(), "EnergyCell not charged!");
 
         // Test fail no recipe
         let mut cell = get_charged_cell();
@@ -1058,7 +1764,8 @@ mod tests {
         let result = combinator.try_make(request, &mut cell);
         assert!(result.is_err());
         let (err, _, _) = result.err().unwrap();
-        assert!(err.contains("there isn't a recipe for"));
+        assert_eq!(err, CombineError::MissingRecipe(ComplexResourceType::Water));
+        assert!(err.to_string().contains("there isn't a recipe for"));
     }
 
     #[test]
@@ -1079,4 +1786,568 @@ mod tests {
         );
         assert!(generic_complex.to_water().is_ok());
     }
+
+    #[test]
+    fn test_wrapper_enums_display() {
+        let oxygen = Oxygen { _private: () };
+        assert_eq!(format!("{}", oxygen.to_basic()), "Basic Resource Oxygen");
+
+        let oxygen = Oxygen { _private: () };
+        assert_eq!(format!("{}", oxygen.to_generic()), "Basic Resource Oxygen");
+
+        let water = Water { _private: () };
+        assert_eq!(format!("{}", water.to_complex()), "Complex Resource Water");
+
+        let water = Water { _private: () };
+        assert_eq!(format!("{}", water.to_generic()), "Complex Resource Water");
+    }
+
+    #[test]
+    fn test_wrapper_enums_resource_trait() {
+        let basic = Hydrogen { _private: () }.to_basic();
+        assert_eq!(basic.to_static_str(), "Hydrogen");
+
+        let complex = Diamond { _private: () }.to_complex();
+        assert_eq!(complex.to_static_str(), "Diamond");
+
+        let resources: Vec<Box<dyn Resource>> = vec![
+            Box::new(Carbon { _private: () }.to_generic()),
+            Box::new(Life { _private: () }.to_generic()),
+        ];
+        let names: Vec<&str> = resources.iter().map(|r| r.to_static_str()).collect();
+        assert_eq!(names, vec!["Carbon", "Life"]);
+    }
+
+    #[test]
+    fn test_resource_type_name_and_category() {
+        let water = ResourceType::Complex(ComplexResourceType::Water);
+        assert_eq!(water.name(), "Water");
+        assert!(water.is_complex());
+        assert!(!water.is_basic());
+
+        let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+        assert_eq!(carbon.name(), "Carbon");
+        assert!(carbon.is_basic());
+        assert!(!carbon.is_complex());
+
+        assert_eq!(carbon.name(), Carbon { _private: () }.to_static_str());
+    }
+
+    #[test]
+    fn test_recipe_graph_and_generation_rules() {
+        assert_eq!(
+            generation_rules(),
+            vec![
+                BasicResourceType::Oxygen,
+                BasicResourceType::Hydrogen,
+                BasicResourceType::Carbon,
+                BasicResourceType::Silicon
+            ]
+        );
+
+        let graph = recipe_graph();
+        assert_eq!(graph.len(), 6);
+        assert_eq!(
+            graph[0],
+            (
+                ComplexResourceType::Water,
+                ResourceType::Basic(BasicResourceType::Hydrogen),
+                ResourceType::Basic(BasicResourceType::Oxygen)
+            )
+        );
+        assert_eq!(
+            graph[5],
+            (
+                ComplexResourceType::AIPartner,
+                ResourceType::Complex(ComplexResourceType::Robot),
+                ResourceType::Complex(ComplexResourceType::Diamond)
+            )
+        );
+    }
+
+    #[test]
+    fn test_combinator_try_make_generic() {
+        let mut generator = Generator::new();
+        let mut comb = Combinator::new();
+        generator.add(BasicResourceType::Oxygen).unwrap();
+        generator.add(BasicResourceType::Hydrogen).unwrap();
+        comb.add(ComplexResourceType::Water).unwrap();
+
+        // ingredients in the recipe order
+        let h = generator.make_hydrogen(&mut get_charged_cell()).unwrap();
+        let o = generator.make_oxygen(&mut get_charged_cell()).unwrap();
+        let result = comb.try_make_generic(
+            ComplexResourceType::Water,
+            h.to_generic(),
+            o.to_generic(),
+            &mut get_charged_cell(),
+        );
+        assert_eq!(result.unwrap().get_type(), ComplexResourceType::Water);
+
+        // ingredients swapped
+        let h = generator.make_hydrogen(&mut get_charged_cell()).unwrap();
+        let o = generator.make_oxygen(&mut get_charged_cell()).unwrap();
+        let result = comb.try_make_generic(
+            ComplexResourceType::Water,
+            o.to_generic(),
+            h.to_generic(),
+            &mut get_charged_cell(),
+        );
+        assert!(result.is_ok());
+
+        // wrong ingredients are handed back in the original order
+        let o1 = generator.make_oxygen(&mut get_charged_cell()).unwrap();
+        let o2 = generator.make_oxygen(&mut get_charged_cell()).unwrap();
+        let h = generator.make_hydrogen(&mut get_charged_cell()).unwrap();
+        let mut cell = get_charged_cell();
+        let (err, a, b) = comb
+            .try_make_generic(
+                ComplexResourceType::Water,
+                o1.to_generic(),
+                o2.to_generic(),
+                &mut cell,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CombineError::InvalidIngredients(ComplexResourceType::Water)
+        );
+        assert!(a.to_oxygen().is_ok());
+        assert!(b.to_oxygen().is_ok());
+        assert!(cell.is_charged());
+
+        let (_, a, b) = comb
+            .try_make_generic(
+                ComplexResourceType::Diamond,
+                h.to_generic(),
+                Water { _private: () }.to_generic(),
+                &mut cell,
+            )
+            .unwrap_err();
+        assert!(a.to_hydrogen().is_ok());
+        assert!(b.to_water().is_ok());
+    }
+
+    #[test]
+    fn test_try_make_generic_is_order_agnostic() {
+        let mut generator = Generator::new();
+        let mut comb = Combinator::new();
+        generator.add(BasicResourceType::Carbon).unwrap();
+        comb.add(ComplexResourceType::Diamond).unwrap();
+        comb.add(ComplexResourceType::Life).unwrap();
+
+        // identical ingredients
+        let c1 = generator.make_carbon(&mut get_charged_cell()).unwrap();
+        let c2 = generator.make_carbon(&mut get_charged_cell()).unwrap();
+        let diamond = comb.try_make_generic(
+            ComplexResourceType::Diamond,
+            c1.to_generic(),
+            c2.to_generic(),
+            &mut get_charged_cell(),
+        );
+        assert!(diamond.is_ok());
+
+        // a basic and a complex ingredient, in both orders (Life = Water + Carbon)
+        for swap in [false, true] {
+            let water = Water { _private: () }.to_generic();
+            let carbon = generator
+                .make_carbon(&mut get_charged_cell())
+                .unwrap()
+                .to_generic();
+            let (a, b) = if swap {
+                (carbon, water)
+            } else {
+                (water, carbon)
+            };
+            let life =
+                comb.try_make_generic(ComplexResourceType::Life, a, b, &mut get_charged_cell());
+            assert_eq!(life.unwrap().get_type(), ComplexResourceType::Life);
+        }
+    }
+
+    #[test]
+    fn test_fabricate_generic_resource() {
+        let water = GenericResource::fabricate(ResourceType::Complex(ComplexResourceType::Water));
+        assert!(water.to_water().is_ok());
+
+        let silicon = GenericResource::fabricate(ResourceType::Basic(BasicResourceType::Silicon));
+        assert_eq!(
+            silicon.get_type(),
+            ResourceType::Basic(BasicResourceType::Silicon)
+        );
+    }
+
+    #[test]
+    fn test_resource_mass() {
+        assert_eq!(BasicResourceType::Oxygen.mass(), 1);
+        assert_eq!(ComplexResourceType::Water.mass(), 2);
+        assert_eq!(ComplexResourceType::Life.mass(), 3);
+        // AIPartner = Robot (Silicon + Life) + Diamond (Carbon + Carbon)
+        assert_eq!(ComplexResourceType::AIPartner.mass(), 6);
+        assert_eq!(
+            ResourceType::Complex(ComplexResourceType::Dolphin).mass(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_resource_types_ordering() {
+        let set: HashSet<ComplexResourceType> = [
+            ComplexResourceType::AIPartner,
+            ComplexResourceType::Water,
+            ComplexResourceType::Diamond,
+        ]
+        .into_iter()
+        .collect();
+        let mut sorted: Vec<_> = set.into_iter().collect();
+        sorted.sort();
+        assert_eq!(
+            sorted,
+            vec![
+                ComplexResourceType::Diamond,
+                ComplexResourceType::Water,
+                ComplexResourceType::AIPartner
+            ]
+        );
+
+        assert!(BasicResourceType::Oxygen < BasicResourceType::Silicon);
+        assert!(
+            ResourceType::Basic(BasicResourceType::Silicon)
+                < ResourceType::Complex(ComplexResourceType::Diamond)
+        );
+    }
+
+    #[test]
+    fn test_resource_type_from_str() {
+        assert_eq!(
+            resource_type_from_str("AIPartner"),
+            Some(ResourceType::Complex(ComplexResourceType::AIPartner))
+        );
+        assert_eq!(
+            resource_type_from_str("Oxygen"),
+            Some(ResourceType::Basic(BasicResourceType::Oxygen))
+        );
+        assert_eq!(resource_type_from_str("oxygen"), None);
+        assert_eq!(resource_type_from_str("Unobtainium"), None);
+
+        for res in generation_rules() {
+            let ty = ResourceType::Basic(res);
+            assert_eq!(resource_type_from_str(ty.name()), Some(ty));
+        }
+    }
+
+    #[test]
+    fn test_reachable_complex() {
+        let mut generator = Generator::new();
+        let mut combinator = Combinator::new();
+        generator.add(BasicResourceType::Hydrogen).unwrap();
+        generator.add(BasicResourceType::Oxygen).unwrap();
+        combinator.add(ComplexResourceType::Water).unwrap();
+        combinator.add(ComplexResourceType::Life).unwrap();
+        combinator.add(ComplexResourceType::Dolphin).unwrap();
+
+        // no carbon, so no life (and no dolphins)
+        assert_eq!(
+            reachable_complex(&generator, &combinator),
+            HashSet::from([ComplexResourceType::Water])
+        );
+
+        generator.add(BasicResourceType::Carbon).unwrap();
+        assert_eq!(
+            reachable_complex(&generator, &combinator),
+            HashSet::from([
+                ComplexResourceType::Water,
+                ComplexResourceType::Life,
+                ComplexResourceType::Dolphin
+            ])
+        );
+
+        assert!(reachable_complex(&Generator::new(), &combinator).is_empty());
+    }
+
+    #[test]
+    fn test_self_serviceable() {
+        let mut generator = Generator::new();
+        let mut combinator = Combinator::new();
+        generator.add(BasicResourceType::Hydrogen).unwrap();
+        generator.add(BasicResourceType::Oxygen).unwrap();
+        generator.add(BasicResourceType::Carbon).unwrap();
+        combinator.add(ComplexResourceType::Water).unwrap();
+        combinator.add(ComplexResourceType::Life).unwrap();
+        combinator.add(ComplexResourceType::Diamond).unwrap();
+
+        // life is reachable, but needs water first
+        assert_eq!(
+            combinator.self_serviceable(&generator),
+            HashSet::from([ComplexResourceType::Water, ComplexResourceType::Diamond])
+        );
+        assert!(combinator.self_serviceable(&Generator::new()).is_empty());
+    }
+
+    #[test]
+    fn test_recipe_table() {
+        let mut combinator = Combinator::new();
+        assert!(combinator.recipe_table().is_empty());
+
+        combinator.add(ComplexResourceType::Water).unwrap();
+        combinator.add(ComplexResourceType::Life).unwrap();
+
+        let table = combinator.recipe_table();
+        assert_eq!(table.len(), 2);
+        assert_eq!(
+            table[&ComplexResourceType::Water],
+            (
+                ResourceType::Basic(BasicResourceType::Hydrogen),
+                ResourceType::Basic(BasicResourceType::Oxygen)
+            )
+        );
+        assert_eq!(
+            table[&ComplexResourceType::Life],
+            ComplexResourceType::Life.request_signature()
+        );
+    }
+
+    #[test]
+    fn test_as_resource_type() {
+        let oxygen = ResourceType::Basic(BasicResourceType::Oxygen);
+        let water = ResourceType::Complex(ComplexResourceType::Water);
+
+        let generic = GenericResource::fabricate(oxygen);
+        assert_eq!(generic.as_resource_type(), oxygen);
+        let GenericResource::BasicResources(basic) = generic else {
+            panic!("expected a basic resource");
+        };
+        assert_eq!(basic.as_resource_type(), oxygen);
+
+        let generic = GenericResource::fabricate(water);
+        assert_eq!(generic.as_resource_type(), water);
+        let GenericResource::ComplexResources(complex) = generic else {
+            panic!("expected a complex resource");
+        };
+        assert_eq!(complex.as_resource_type(), water);
+    }
+
+    #[test]
+    fn test_is_type() {
+        let water = GenericResource::fabricate(ResourceType::Complex(ComplexResourceType::Water));
+        assert!(water.is_type(ResourceType::Complex(ComplexResourceType::Water)));
+        assert!(water.is_complex_type(ComplexResourceType::Water));
+        assert!(!water.is_complex_type(ComplexResourceType::Life));
+        assert!(!water.is_basic_type(BasicResourceType::Oxygen));
+
+        let oxygen = GenericResource::fabricate(ResourceType::Basic(BasicResourceType::Oxygen));
+        assert!(oxygen.is_basic_type(BasicResourceType::Oxygen));
+        assert!(!oxygen.is_type(ResourceType::Basic(BasicResourceType::Carbon)));
+    }
+
+    #[test]
+    fn test_production_mode() {
+        let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+        let oxygen = ResourceType::Basic(BasicResourceType::Oxygen);
+        let diamond = ResourceType::Complex(ComplexResourceType::Diamond);
+        let water = ResourceType::Complex(ComplexResourceType::Water);
+        assert_eq!(carbon.production_mode(), ProductionMode::Generatable);
+        assert_eq!(diamond.production_mode(), ProductionMode::Combinable);
+
+        let mut generator = Generator::new();
+        let mut combinator = Combinator::new();
+        generator.add(BasicResourceType::Carbon).unwrap();
+        combinator.add(ComplexResourceType::Diamond).unwrap();
+
+        assert_eq!(
+            production_mode_for(carbon, &generator, &combinator),
+            Some(ProductionMode::Generatable)
+        );
+        assert_eq!(
+            production_mode_for(diamond, &generator, &combinator),
+            Some(ProductionMode::Combinable)
+        );
+        assert_eq!(production_mode_for(oxygen, &generator, &combinator), None);
+        assert_eq!(production_mode_for(water, &generator, &combinator), None);
+    }
+
+    #[test]
+    fn test_craft_plan() {
+        use CraftStep::{Combine, Generate};
+
+        assert_eq!(
+            ComplexResourceType::Life.craft_plan().steps,
+            vec![
+                Generate(BasicResourceType::Hydrogen),
+                Generate(BasicResourceType::Oxygen),
+                Combine(ComplexResourceType::Water),
+                Generate(BasicResourceType::Carbon),
+                Combine(ComplexResourceType::Life),
+            ]
+        );
+
+        for (complex, _, _) in recipe_graph() {
+            let plan = complex.craft_plan();
+            assert_eq!(plan.cells_needed(), complex.cells_needed());
+            assert_eq!(plan.steps.last(), Some(&Combine(complex)));
+        }
+    }
+
+    #[test]
+    fn test_try_make_into() {
+        let mut combinator = Combinator::new();
+        combinator.add(ComplexResourceType::Diamond).unwrap();
+        let mut bag = ResourceBag::new();
+        let mut cell = EnergyCell::new();
+        let carbon = || {
+            GenericResource::fabricate(ResourceType::Basic(BasicResourceType::Carbon))
+                .to_carbon()
+                .unwrap()
+        };
+
+        // uncharged cell: the inputs go back into the bag
+        let req = ComplexResourceRequest::Diamond(carbon(), carbon());
+        assert_eq!(
+            combinator
+                .try_make_into(req, &mut cell, &mut bag)
+                .unwrap_err(),
+            CombineError::CellNotCharged
+        );
+        assert_eq!(bag.len(), 2);
+
+        cell.charge(Sunray::new());
+        let req = ComplexResourceRequest::Diamond(carbon(), carbon());
+        assert!(combinator.try_make_into(req, &mut cell, &mut bag).is_ok());
+        assert_eq!(bag.len(), 2);
+    }
+
+    #[test]
+    fn test_complex_resource_request_build() {
+        let fabricate = |basic| GenericResource::fabricate(ResourceType::Basic(basic));
+
+        let req = ComplexResourceRequest::build(
+            ComplexResourceType::Water,
+            fabricate(BasicResourceType::Oxygen),
+            fabricate(BasicResourceType::Hydrogen),
+        )
+        .unwrap();
+        assert!(matches!(req, ComplexResourceRequest::Water(..)));
+        assert_eq!(req.validate(), Ok(()));
+
+        let (a, b) = ComplexResourceRequest::build(
+            ComplexResourceType::Water,
+            fabricate(BasicResourceType::Oxygen),
+            fabricate(BasicResourceType::Carbon),
+        )
+        .unwrap_err();
+        assert_eq!(a.get_type(), ResourceType::Basic(BasicResourceType::Oxygen));
+        assert_eq!(b.get_type(), ResourceType::Basic(BasicResourceType::Carbon));
+    }
+
+    #[test]
+    fn test_complex_resource_request_from_bag() {
+        let mut bag = ResourceBag::new();
+        bag.insert(GenericResource::fabricate(ResourceType::Basic(
+            BasicResourceType::Oxygen,
+        )));
+
+        // missing hydrogen: the oxygen stays in the bag
+        assert!(ComplexResourceRequest::from_bag(ComplexResourceType::Water, &mut bag).is_none());
+        assert_eq!(bag.len(), 1);
+
+        bag.insert(GenericResource::fabricate(ResourceType::Basic(
+            BasicResourceType::Hydrogen,
+        )));
+        let req = ComplexResourceRequest::from_bag(ComplexResourceType::Water, &mut bag).unwrap();
+        assert!(matches!(req, ComplexResourceRequest::Water(..)));
+        assert!(bag.is_empty());
+
+        let mut combinator = Combinator::new();
+        combinator.add(ComplexResourceType::Water).unwrap();
+        let water = combinator.try_make(req, &mut get_charged_cell()).unwrap();
+        assert_eq!(water.get_type(), ComplexResourceType::Water);
+    }
+
+    #[test]
+    fn test_clear_recipes() {
+        let mut generator = Generator::new();
+        let mut combinator = Combinator::new();
+        generator.add(BasicResourceType::Carbon).unwrap();
+        combinator.add(ComplexResourceType::Diamond).unwrap();
+
+        generator.clear();
+        combinator.clear();
+        assert!(generator.all_available_recipes().is_empty());
+        assert!(combinator.all_available_recipes().is_empty());
+
+        // recipes can be added again
+        assert!(generator.add(BasicResourceType::Carbon).is_ok());
+        assert!(combinator.add(ComplexResourceType::Diamond).is_ok());
+    }
+
+    #[test]
+    fn test_request_signature_and_describe() {
+        assert_eq!(
+            ComplexResourceType::Robot.request_signature(),
+            (
+                ResourceType::Basic(BasicResourceType::Silicon),
+                ResourceType::Complex(ComplexResourceType::Life)
+            )
+        );
+        assert_eq!(
+            ComplexResourceType::Robot.describe(),
+            "Robot = Silicon + Life"
+        );
+        assert_eq!(
+            ComplexResourceType::Water.describe(),
+            "Water = Hydrogen + Oxygen"
+        );
+    }
+
+    #[test]
+    fn test_complex_resource_request_validate() {
+        let mut generator = Generator::new();
+        generator.add(BasicResourceType::Hydrogen).unwrap();
+        generator.add(BasicResourceType::Oxygen).unwrap();
+        let mut cell = get_charged_cell();
+        let hydrogen = generator.make_hydrogen(&mut cell).unwrap();
+        let mut cell = get_charged_cell();
+        let oxygen = generator.make_oxygen(&mut cell).unwrap();
+
+        let req = ComplexResourceRequest::Water(hydrogen, oxygen);
+        assert_eq!(req.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_recipes_sorted() {
+        let mut generator = Generator::new();
+        generator.add(BasicResourceType::Silicon).unwrap();
+        generator.add(BasicResourceType::Oxygen).unwrap();
+        generator.add(BasicResourceType::Carbon).unwrap();
+        assert_eq!(
+            generator.recipes_sorted(),
+            vec![
+                BasicResourceType::Oxygen,
+                BasicResourceType::Carbon,
+                BasicResourceType::Silicon
+            ]
+        );
+
+        let mut combinator = Combinator::new();
+        combinator.add(ComplexResourceType::AIPartner).unwrap();
+        combinator.add(ComplexResourceType::Diamond).unwrap();
+        assert_eq!(
+            combinator.recipes_sorted(),
+            vec![ComplexResourceType::Diamond, ComplexResourceType::AIPartner]
+        );
+    }
+
+    #[test]
+    fn test_cells_needed() {
+        assert_eq!(
+            ResourceType::Basic(BasicResourceType::Oxygen).cells_needed(),
+            1
+        );
+        assert_eq!(ComplexResourceType::Water.cells_needed(), 3);
+        assert_eq!(ComplexResourceType::Diamond.cells_needed(), 3);
+        // Life = Water (3) + Carbon (1) + 1
+        assert_eq!(ComplexResourceType::Life.cells_needed(), 5);
+        // AIPartner = Robot (Silicon + Life + 1 = 7) + Diamond (3) + 1
+        assert_eq!(ComplexResourceType::AIPartner.cells_needed(), 11);
+    }
 }
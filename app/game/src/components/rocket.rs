@@ -27,4 +27,16 @@ impl Rocket {
     pub(crate) fn new(energy_cell: &mut EnergyCell) -> Result<Rocket, String> {
         energy_cell.discharge().map(|()| Rocket { _private: () })
     }
+
+    /// # Test utilities - Do not use in production code
+    ///
+    /// Creates a new instance of [Rocket] from outside this crate, without consuming
+    /// any energy cell, to build the protocol messages in tests.
+    ///
+    /// Only available with the `test-utils` feature.
+    #[cfg(feature = "test-utils")]
+    #[must_use]
+    pub fn for_test() -> Rocket {
+        Rocket { _private: () }
+    }
 }
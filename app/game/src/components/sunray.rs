@@ -1,7 +1,12 @@
 /// Represents a sunray object, instanciable by the orchestrator.
+///
+/// A sunray carries an amount of energy (see [`Sunray::energy`]), modelling the intensity
+/// of the light: a plain sunray carries a single charge, a stronger one can fill more of an
+/// [`EnergyCell`](crate::components::energy_cell::EnergyCell).
 #[derive(Debug)]
 pub struct Sunray {
-    _private: (),
+    // the number of charges carried by the sunray
+    energy: u32,
 }
 #[allow(dead_code)]
 impl Default for Sunray {
@@ -18,8 +23,31 @@ impl Sunray {
     ///
     /// # Returns
     ///
-    /// Returns a new instance of [Sunray].
+    /// Returns a new instance of [Sunray], carrying a single charge.
     pub(crate) fn new() -> Sunray {
-        Sunray { _private: () }
+        Self::with_energy(1)
+    }
+
+    /// Creates a new [Sunray] carrying `energy` charges.
+    pub(crate) fn with_energy(energy: u32) -> Sunray {
+        Sunray { energy }
+    }
+
+    /// Returns the number of charges carried by the sunray.
+    #[must_use]
+    pub fn energy(&self) -> u32 {
+        self.energy
+    }
+
+    /// # Test utilities - Do not use in production code
+    ///
+    /// Creates a new instance of [Sunray] from outside this crate, to build the
+    /// protocol messages in tests.
+    ///
+    /// Only available with the `test-utils` feature.
+    #[cfg(feature = "test-utils")]
+    #[must_use]
+    pub fn for_test() -> Sunray {
+        Self::new()
     }
 }
@@ -7,10 +7,12 @@
 //! It provides mechanisms to create log events with timestamps, participants,
 //! and payloads, as well as utilities to emit these events using the `log` crate
 //! for integration with various logging backends.
-use std::collections::BTreeMap;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, VecDeque};
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, PoisonError};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use std::fmt;
@@ -36,7 +38,10 @@ pub enum ActorType {
 
 /// Standardized log channels shared across the application.
 /// Note: "event" here means a series of messages with a specific effect
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Channels are ordered from the most to the least severe (`Error < Warning < ... < Trace`),
+/// like [`log::Level`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Channel {
     /// Anything that leads to a panic
     Error,
@@ -53,6 +58,28 @@ pub enum Channel {
     Trace,
 }
 
+// the most verbose channel emitted by `LogEvent::emit`, as a `Channel` discriminant
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(Channel::Trace as u8);
+
+/// Sets the most verbose [`Channel`] emitted by [`LogEvent::emit`], process-wide:
+/// events on more verbose channels are dropped before reaching the `log` backend
+/// (e.g. with [`Channel::Info`], [`Channel::Debug`] and [`Channel::Trace`] events are dropped).
+///
+/// The default level is [`Channel::Trace`], which emits everything and leaves the
+/// filtering to the configured logger.
+pub fn set_log_level(level: Channel) {
+    LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+impl Channel {
+    /// Returns `true` if events on this channel are emitted at the current log level
+    /// (see [`set_log_level`]).
+    #[must_use]
+    pub fn is_enabled(self) -> bool {
+        self as u8 <= LOG_LEVEL.load(Ordering::Relaxed)
+    }
+}
+
 /// High-level event categories.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EventType {
@@ -82,11 +109,94 @@ pub enum EventType {
     UserToExplorer,
     /// User command to orchestrator
     UserToOrchestrator,
+
+    /// Internal user action (e.g. a GUI interaction not yet turned into a command)
+    InternalUserAction,
+
+    /// Message sent by the orchestrator to every planet or explorer at once
+    OrchestratorBroadcast,
+    /// User command sent to every entity at once
+    UserBroadcast,
+}
+
+impl EventType {
+    /// Returns the payload keys recommended for events of this type, so that logs from
+    /// different implementations can be analyzed together.
+    ///
+    /// The schema is advisory: extra keys are always allowed, and missing ones are only
+    /// reported by [`LogEvent::validate_payload`].
+    #[must_use]
+    pub fn payload_schema(&self) -> &'static [&'static str] {
+        match self {
+            EventType::MessagePlanetToOrchestrator | EventType::MessageOrchestratorToPlanet => {
+                &["planet_id", MSG_KIND_KEY]
+            }
+            EventType::MessagePlanetToExplorer => &["planet_id", "explorer_id", MSG_KIND_KEY],
+            EventType::MessageOrchestratorToExplorer
+            | EventType::MessageExplorerToPlanet
+            | EventType::MessageExplorerToOrchestrator => &["explorer_id", MSG_KIND_KEY],
+            EventType::OrchestratorBroadcast => &[MSG_KIND_KEY],
+            EventType::InternalPlanetAction
+            | EventType::InternalExplorerAction
+            | EventType::InternalOrchestratorAction
+            | EventType::InternalUserAction => &["action"],
+            EventType::UserToPlanet
+            | EventType::UserToExplorer
+            | EventType::UserToOrchestrator
+            | EventType::UserBroadcast => &["command"],
+        }
+    }
+
+    /// Returns the event category of an interaction from `sender` to `receiver`,
+    /// or [None] if the pair doesn't describe a valid interaction
+    /// (e.g. planets and explorers can't broadcast, and nobody sends messages to the user).
+    ///
+    /// [`ActorType::SelfActor`] as receiver maps to the internal action of the sender.
+    #[must_use]
+    pub fn from_actors(sender: &ActorType, receiver: &ActorType) -> Option<EventType> {
+        use ActorType::{Broadcast, Explorer, Orchestrator, Planet, SelfActor, User};
+
+        match (sender, receiver) {
+            (Planet, Orchestrator) => Some(EventType::MessagePlanetToOrchestrator),
+            (Orchestrator, Planet) => Some(EventType::MessageOrchestratorToPlanet),
+            (Planet, Explorer) => Some(EventType::MessagePlanetToExplorer),
+            (Orchestrator, Explorer) => Some(EventType::MessageOrchestratorToExplorer),
+            (Explorer, Planet) => Some(EventType::MessageExplorerToPlanet),
+            (Explorer, Orchestrator) => Some(EventType::MessageExplorerToOrchestrator),
+
+            (Planet, SelfActor) => Some(EventType::InternalPlanetAction),
+            (Explorer, SelfActor) => Some(EventType::InternalExplorerAction),
+            (Orchestrator, SelfActor) => Some(EventType::InternalOrchestratorAction),
+            (User, SelfActor) => Some(EventType::InternalUserAction),
+
+            (User, Planet) => Some(EventType::UserToPlanet),
+            (User, Explorer) => Some(EventType::UserToExplorer),
+            (User, Orchestrator) => Some(EventType::UserToOrchestrator),
+
+            (Orchestrator, Broadcast) => Some(EventType::OrchestratorBroadcast),
+            (User, Broadcast) => Some(EventType::UserBroadcast),
+
+            _ => None,
+        }
+    }
 }
 
 /// Simple key–value payload: string → string.
 pub type Payload = BTreeMap<String, String>;
 
+/// Merges two payloads, e.g. a base context shared by many events with per-event details.
+///
+/// On key collision, the value from `extra` **overrides** the one from `base`.
+#[must_use]
+pub fn merge_payloads(mut base: Payload, extra: Payload) -> Payload {
+    base.extend(extra);
+    base
+}
+
+/// Reserved payload key holding the kind of the message that originated the event
+/// (e.g. `"AvailableEnergyCellRequest"`), see [`LogEvent::with_message_kind`].
+pub const MSG_KIND_KEY: &str = "msg_kind";
+
 /// Participant in a log event. Either side of an interaction can be absent.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Participant {
@@ -155,6 +265,42 @@ impl LogEvent {
         }
     }
 
+    /// Attaches the kind of the message that originated the event to the payload,
+    /// under the reserved [`MSG_KIND_KEY`] key, replacing any previous value.
+    ///
+    /// Meant to be chained onto [`LogEvent::new`] with the message kind enums
+    /// (e.g. `ExplorerToPlanetKind::from(&msg)`), so that logs can be filtered by message type.
+    #[must_use]
+    pub fn with_message_kind(mut self, kind: impl fmt::Display) -> Self {
+        self.payload
+            .insert(MSG_KIND_KEY.to_string(), kind.to_string());
+        self
+    }
+
+    /// Adds the entries of `extra` to the event payload (see [`merge_payloads`]):
+    /// on key collision, the value from `extra` overrides the existing one.
+    #[must_use]
+    pub fn extend_payload(mut self, extra: Payload) -> Self {
+        self.payload.extend(extra);
+        self
+    }
+
+    /// Checks the payload against the recommended schema of the event type
+    /// (see [`EventType::payload_schema`]).
+    ///
+    /// # Returns
+    /// A warning for each recommended key missing from the payload, empty if the payload
+    /// follows the schema. This is advisory only: the event can be emitted anyway.
+    #[must_use]
+    pub fn validate_payload(&self) -> Vec<String> {
+        self.event_type
+            .payload_schema()
+            .iter()
+            .filter(|key| !self.payload.contains_key(**key))
+            .map(|key| format!("missing payload key `{key}` for {:?}", self.event_type))
+            .collect()
+    }
+
     /// Convenience: broadcast from a known sender to no specific receiver.
     #[must_use]
     pub fn broadcast(
@@ -189,6 +335,27 @@ impl LogEvent {
         )
     }
 
+    /// Convenience: emit an event for an internal action of an actor (e.g. a planet charging
+    /// a cell), i.e. a [`LogEvent::self_directed`] event built from the actor type and id.
+    ///
+    /// The matching `event_type` is given by
+    /// [`EventType::from_actors`]`(&actor, &ActorType::SelfActor)`.
+    #[must_use]
+    pub fn internal(
+        actor: ActorType,
+        actor_id: impl Into<ID>,
+        event_type: EventType,
+        channel: Channel,
+        payload: Payload,
+    ) -> Self {
+        Self::self_directed(
+            Participant::new(actor, actor_id),
+            event_type,
+            channel,
+            payload,
+        )
+    }
+
     #[must_use]
     /// Generate a deterministic identifier from an arbitrary string.
     pub fn id_from_str(s: &str) -> u64 {
@@ -202,9 +369,16 @@ impl LogEvent {
     /// Uses the `Debug` representation to preserve all structured fields. If no
     /// logger is initialized by the final binary this is a no-op, which is fine
     /// for library consumers.
+    ///
+    /// Events on a channel more verbose than the level set with [`set_log_level`]
+    /// are not emitted.
     pub fn emit(&self) {
         use Channel::{Debug, Error, Info, Trace, Warning};
 
+        if !self.channel.is_enabled() {
+            return;
+        }
+
         match self.channel {
             Error => log::error!("{self:?}"),
             Warning => log::warn!("{self:?}"),
@@ -235,9 +409,84 @@ impl fmt::Display for LogEvent {
     }
 }
 
+/// A destination for [`LogEvent`]s, besides the `log` crate used by [`LogEvent::emit`]
+/// (e.g. an in-memory collector feeding a GUI).
+///
+/// Sinks take `&self` and are `Send + Sync`, so that a single sink can be shared (e.g. in an
+/// `Arc`) by all the actors of the game, each running on its own thread.
+pub trait LogSink: Send + Sync {
+    /// Records an event.
+    fn record(&self, event: LogEvent);
+}
+
+/// A bounded, thread-safe in-memory collector of the most recent [`LogEvent`]s,
+/// e.g. for a "recent events" panel.
+///
+/// Once `capacity` events are stored, recording a new one evicts the oldest.
+#[derive(Debug)]
+pub struct LogBuffer {
+    capacity: usize,
+    events: Mutex<VecDeque<LogEvent>>,
+}
+
+impl LogBuffer {
+    /// Creates an empty buffer holding at most `capacity` events.
+    /// A buffer with no capacity discards every event.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            events: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Returns the maximum number of events held by the buffer.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns (clones of) the `n` most recent events, from the oldest to the newest.
+    #[must_use]
+    pub fn recent(&self, n: usize) -> Vec<LogEvent> {
+        let events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        events
+            .iter()
+            .skip(events.len().saturating_sub(n))
+            .cloned()
+            .collect()
+    }
+
+    /// Removes and returns all the stored events, from the oldest to the newest.
+    pub fn drain(&self) -> Vec<LogEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(..)
+            .collect()
+    }
+}
+
+impl LogSink for LogBuffer {
+    /// Stores the event, evicting the oldest one if the buffer is full.
+    fn record(&self, event: LogEvent) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+    use crate::protocols::planet_explorer::ExplorerToPlanetKind;
     use log::{Level, Log, Metadata, Record};
     use std::sync::{Mutex, Once};
 
@@ -355,6 +604,21 @@ mod tests {
         assert_eq!(event.receiver, Some(actor));
     }
 
+    #[test]
+    fn internal_event_uses_the_same_actor() {
+        let event = LogEvent::internal(
+            ActorType::Planet,
+            3u32,
+            EventType::InternalPlanetAction,
+            Channel::Debug,
+            sample_payload(),
+        );
+
+        let actor = sample_participant(ActorType::Planet, 3);
+        assert_eq!(event.sender, Some(actor.clone()));
+        assert_eq!(event.receiver, Some(actor));
+    }
+
     #[test]
     fn display_formats_optional_participants() {
         let mut event = LogEvent::system(
@@ -392,4 +656,147 @@ mod tests {
         assert!(message.contains("LogEvent"));
         assert!(message.contains("sender:"));
     }
+
+    #[test]
+    fn event_type_from_actors_covers_the_matrix() {
+        assert_eq!(
+            EventType::from_actors(&ActorType::Orchestrator, &ActorType::Planet),
+            Some(EventType::MessageOrchestratorToPlanet)
+        );
+        assert_eq!(
+            EventType::from_actors(&ActorType::User, &ActorType::SelfActor),
+            Some(EventType::InternalUserAction)
+        );
+        assert_eq!(
+            EventType::from_actors(&ActorType::Orchestrator, &ActorType::Broadcast),
+            Some(EventType::OrchestratorBroadcast)
+        );
+        assert_eq!(
+            EventType::from_actors(&ActorType::Planet, &ActorType::Broadcast),
+            None
+        );
+        assert_eq!(
+            EventType::from_actors(&ActorType::Planet, &ActorType::User),
+            None
+        );
+    }
+
+    #[test]
+    fn with_message_kind_uses_reserved_key() {
+        let event = LogEvent::system(
+            EventType::MessageExplorerToPlanet,
+            Channel::Trace,
+            sample_payload(),
+        )
+        .with_message_kind(ExplorerToPlanetKind::AvailableEnergyCellRequest);
+
+        assert_eq!(
+            event.payload.get(MSG_KIND_KEY).map(String::as_str),
+            Some("AvailableEnergyCellRequest")
+        );
+    }
+
+    #[test]
+    fn validate_payload_reports_missing_keys() {
+        let mut payload = Payload::new();
+        payload.insert("explorer_id".to_string(), "7".to_string());
+        let event = LogEvent::new(
+            Some(sample_participant(ActorType::Explorer, 7)),
+            Some(sample_participant(ActorType::Planet, 1)),
+            EventType::MessageExplorerToPlanet,
+            Channel::Trace,
+            payload,
+        );
+
+        let warnings = event.validate_payload();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(MSG_KIND_KEY));
+
+        let event = event.with_message_kind(ExplorerToPlanetKind::SupportedResourceRequest);
+        assert!(event.validate_payload().is_empty());
+    }
+
+    #[test]
+    fn merge_payloads_extra_overrides_base() {
+        let mut base = Payload::new();
+        base.insert("planet_id".to_string(), "1".to_string());
+        base.insert("reason".to_string(), "base".to_string());
+        let mut extra = Payload::new();
+        extra.insert("reason".to_string(), "extra".to_string());
+
+        let merged = merge_payloads(base.clone(), extra.clone());
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged["planet_id"], "1");
+        assert_eq!(merged["reason"], "extra");
+
+        let event = LogEvent::system(EventType::InternalPlanetAction, Channel::Debug, base)
+            .extend_payload(extra);
+        assert_eq!(event.payload, merged);
+    }
+
+    #[test]
+    fn log_level_gates_verbose_channels() {
+        assert!(Channel::Error < Channel::Warning && Channel::Debug < Channel::Trace);
+        assert!(Channel::Trace.is_enabled());
+
+        set_log_level(Channel::Info);
+        assert!(Channel::Error.is_enabled());
+        assert!(Channel::Info.is_enabled());
+        assert!(!Channel::Debug.is_enabled());
+        assert!(!Channel::Trace.is_enabled());
+
+        set_log_level(Channel::Trace);
+        assert!(Channel::Trace.is_enabled());
+    }
+
+    #[test]
+    fn explorer_to_orchestrator_log_event() {
+        let msg = ExplorerToOrchestrator::<()>::NeighborsRequest {
+            explorer_id: 4,
+            current_planet_id: 2,
+        };
+
+        let event = msg.to_log_event(0);
+        assert_eq!(
+            event.sender,
+            Some(Participant::new(ActorType::Explorer, 4u32))
+        );
+        assert_eq!(
+            event.receiver,
+            Some(Participant::new(ActorType::Orchestrator, 0u32))
+        );
+        assert_eq!(event.event_type, EventType::MessageExplorerToOrchestrator);
+        assert_eq!(event.payload[MSG_KIND_KEY], "NeighborsRequest");
+        assert!(event.validate_payload().is_empty());
+    }
+
+    #[test]
+    fn log_buffer_evicts_oldest() {
+        let buffer = LogBuffer::new(2);
+        let event = |key: &str| {
+            let mut payload = Payload::new();
+            payload.insert(key.into(), String::new());
+            LogEvent::system(EventType::InternalPlanetAction, Channel::Debug, payload)
+        };
+
+        assert!(buffer.recent(5).is_empty());
+        for key in ["a", "b", "c"] {
+            buffer.record(event(key));
+        }
+
+        let keys = |events: Vec<LogEvent>| -> Vec<String> {
+            events
+                .into_iter()
+                .flat_map(|event| event.payload.into_keys())
+                .collect()
+        };
+        assert_eq!(keys(buffer.recent(5)), ["b", "c"]);
+        assert_eq!(keys(buffer.recent(1)), ["c"]);
+        assert_eq!(buffer.drain().len(), 2);
+        assert!(buffer.drain().is_empty());
+
+        let empty = LogBuffer::new(0);
+        empty.record(event("a"));
+        assert!(empty.recent(1).is_empty());
+    }
 }
@@ -3,12 +3,20 @@
 //! Defines the types of messages exchanged of the full duplex communication channel
 //! between the Orchestrator and the Explorers
 //! For a more detailed view of the interactions between these two entities, visit the communications [diagrams](https://github.com/unitn-ap-2025/common/blob/main/MESSAGE_DIAGRAMS.md)
-use crate::components::resource::{BasicResourceType, ComplexResourceType};
+use crate::components::bag::BagView;
+use crate::components::resource::{
+    BasicResourceType, CombineError, ComplexResourceType, GenerateError,
+};
+use crate::logging::{ActorType, Channel, EventType, LogEvent, Participant, Payload};
 use crate::protocols::planet_explorer::ExplorerToPlanet;
 use crate::utils::ID;
-use crossbeam_channel::Sender;
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use enum_as_inner::EnumAsInner;
 use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use strum::VariantArray;
 use strum_macros::EnumDiscriminants;
 
 #[cfg(doc)]
@@ -17,7 +25,12 @@ use crate::components::resource::{BasicResource, ComplexResource};
 /// This enum describes all possible messages from the Orchestrator to an Explorer
 #[derive(Debug, EnumAsInner, EnumDiscriminants)]
 #[strum_discriminants(name(OrchestratorToExplorerKind))]
-#[strum_discriminants(derive(Hash))]
+#[strum_discriminants(derive(
+    Hash,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::VariantArray
+))]
 pub enum OrchestratorToExplorer {
     /// This variant is used to start an Explorer AI
     ///
@@ -110,7 +123,12 @@ pub enum OrchestratorToExplorer {
 /// This enum describes all possible messages from an Explorer to the Orchestrator
 #[derive(Debug, EnumAsInner, EnumDiscriminants)]
 #[strum_discriminants(name(ExplorerToOrchestratorKind))]
-#[strum_discriminants(derive(Hash))]
+#[strum_discriminants(derive(
+    Hash,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::VariantArray
+))]
 pub enum ExplorerToOrchestrator<T> {
     /// This variant is used to acknowledge the starting of the Explorer AI
     ///
@@ -184,8 +202,8 @@ pub enum ExplorerToOrchestrator<T> {
         explorer_id: ID,
         ///A Result consisting of: [Ok] if the requested resource has been generated and added to the Explorer Bag
         ///
-        ///An [Err] String if the requested resource has not been generated
-        generated: Result<(), String>,
+        ///An [Err] with the [`ResourceError`] telling why the requested resource has not been generated
+        generated: Result<(), ResourceError>,
     },
     /// This variant is used to send the generated [`ComplexResource`] asked by the Orchestrator
     ///
@@ -195,11 +213,14 @@ pub enum ExplorerToOrchestrator<T> {
         explorer_id: ID,
         ///A Result consisting of: [Ok] if the requested resource has been generated and added to the Explorer Bag
         ///
-        ///An [Err] String if the requested resource has not been generated
-        generated: Result<(), String>,
+        ///An [Err] with the [`ResourceError`] telling why the requested resource has not been generated
+        generated: Result<(), ResourceError>,
     },
     /// This message is for passing around the bag content and has been implemented with a generic type to let the group the freedom to implement the methods on it
     ///
+    /// Implementing [`BagView`] for the bag type is recommended, so that tools can read the bag content
+    /// (see [`ExplorerToOrchestrator::bag_view`])
+    ///
     /// **Response To**: [`OrchestratorToExplorer::BagContentRequest`]
     BagContentResponse {
         ///The ID of the explorer sending the message
@@ -253,4 +274,530 @@ impl<T> ExplorerToOrchestrator<T> {
             | Self::StopExplorerAIResult { explorer_id, .. } => *explorer_id,
         }
     }
+
+    /// Builds the [`LogEvent`] describing this message, sent by its explorer to the
+    /// orchestrator with id `orchestrator_id`, on the [`Channel::Trace`] channel.
+    ///
+    /// The payload holds the `explorer_id` and the message kind (see [`LogEvent::with_message_kind`]),
+    /// the content of the message is left out.
+    #[must_use]
+    pub fn to_log_event(&self, orchestrator_id: ID) -> LogEvent {
+        let kind = ExplorerToOrchestratorKind::from(self);
+        let explorer_id = self.explorer_id();
+
+        LogEvent::new(
+            Some(Participant::new(ActorType::Explorer, explorer_id)),
+            Some(Participant::new(ActorType::Orchestrator, orchestrator_id)),
+            kind.event_type(),
+            Channel::Trace,
+            Payload::from([("explorer_id".to_string(), explorer_id.to_string())]),
+        )
+        .with_message_kind(kind)
+    }
+}
+
+impl<T: BagView> ExplorerToOrchestrator<T> {
+    /// Returns a [`BagView`] over the bag content if this is a
+    /// [`ExplorerToOrchestrator::BagContentResponse`], [None] otherwise.
+    #[must_use]
+    pub fn bag_view(&self) -> Option<&dyn BagView> {
+        match self {
+            Self::BagContentResponse { bag_content, .. } => Some(bag_content),
+            _ => None,
+        }
+    }
+}
+
+impl OrchestratorToExplorerKind {
+    /// Returns every kind of message, in declaration order
+    /// (e.g. to check that a handler exists for each of them).
+    #[must_use]
+    pub fn all() -> &'static [OrchestratorToExplorerKind] {
+        Self::VARIANTS
+    }
+
+    /// Returns the [`EventType`] to use when logging a message of this kind.
+    #[must_use]
+    pub fn event_type(&self) -> EventType {
+        EventType::MessageOrchestratorToExplorer
+    }
+
+    /// Returns the kind of the response an explorer is expected to send to a message of this kind,
+    /// or [None] if the message is itself a response.
+    #[must_use]
+    pub fn expected_response(&self) -> Option<ExplorerToOrchestratorKind> {
+        match self {
+            Self::StartExplorerAI => Some(ExplorerToOrchestratorKind::StartExplorerAIResult),
+            Self::ResetExplorerAI => Some(ExplorerToOrchestratorKind::ResetExplorerAIResult),
+            Self::KillExplorer => Some(ExplorerToOrchestratorKind::KillExplorerResult),
+            Self::StopExplorerAI => Some(ExplorerToOrchestratorKind::StopExplorerAIResult),
+            Self::MoveToPlanet => Some(ExplorerToOrchestratorKind::MovedToPlanetResult),
+            Self::CurrentPlanetRequest => Some(ExplorerToOrchestratorKind::CurrentPlanetResult),
+            Self::SupportedResourceRequest => {
+                Some(ExplorerToOrchestratorKind::SupportedResourceResult)
+            }
+            Self::SupportedCombinationRequest => {
+                Some(ExplorerToOrchestratorKind::SupportedCombinationResult)
+            }
+            Self::GenerateResourceRequest => {
+                Some(ExplorerToOrchestratorKind::GenerateResourceResponse)
+            }
+            Self::CombineResourceRequest => {
+                Some(ExplorerToOrchestratorKind::CombineResourceResponse)
+            }
+            Self::BagContentRequest => Some(ExplorerToOrchestratorKind::BagContentResponse),
+            Self::NeighborsResponse => None,
+        }
+    }
+
+    /// Returns the kind of the request a message of this kind is a response to,
+    /// or [None] if the message is a request.
+    ///
+    /// [`OrchestratorToExplorer::MoveToPlanet`] is both: it answers a
+    /// [`ExplorerToOrchestrator::TravelToPlanetRequest`] in normal mode, but it's also sent
+    /// unsolicited in manual mode.
+    #[must_use]
+    pub fn responds_to(&self) -> Option<ExplorerToOrchestratorKind> {
+        match self {
+            Self::MoveToPlanet => Some(ExplorerToOrchestratorKind::TravelToPlanetRequest),
+            Self::NeighborsResponse => Some(ExplorerToOrchestratorKind::NeighborsRequest),
+            _ => None,
+        }
+    }
+}
+
+impl ExplorerToOrchestratorKind {
+    /// Returns every kind of message, in declaration order
+    /// (e.g. to check that a handler exists for each of them).
+    #[must_use]
+    pub fn all() -> &'static [ExplorerToOrchestratorKind] {
+        Self::VARIANTS
+    }
+
+    /// Returns the [`EventType`] to use when logging a message of this kind.
+    #[must_use]
+    pub fn event_type(&self) -> EventType {
+        EventType::MessageExplorerToOrchestrator
+    }
+
+    /// Returns the kind of the response the orchestrator is expected to send to a message of this kind,
+    /// or [None] if the message is itself a response.
+    #[must_use]
+    pub fn expected_response(&self) -> Option<OrchestratorToExplorerKind> {
+        match self {
+            Self::NeighborsRequest => Some(OrchestratorToExplorerKind::NeighborsResponse),
+            Self::TravelToPlanetRequest => Some(OrchestratorToExplorerKind::MoveToPlanet),
+            _ => None,
+        }
+    }
+
+    /// Returns the kind of the request a message of this kind is a response to,
+    /// or [None] if the message is a request.
+    #[must_use]
+    pub fn responds_to(&self) -> Option<OrchestratorToExplorerKind> {
+        match self {
+            Self::StartExplorerAIResult => Some(OrchestratorToExplorerKind::StartExplorerAI),
+            Self::KillExplorerResult => Some(OrchestratorToExplorerKind::KillExplorer),
+            Self::ResetExplorerAIResult => Some(OrchestratorToExplorerKind::ResetExplorerAI),
+            Self::StopExplorerAIResult => Some(OrchestratorToExplorerKind::StopExplorerAI),
+            Self::MovedToPlanetResult => Some(OrchestratorToExplorerKind::MoveToPlanet),
+            Self::CurrentPlanetResult => Some(OrchestratorToExplorerKind::CurrentPlanetRequest),
+            Self::SupportedResourceResult => {
+                Some(OrchestratorToExplorerKind::SupportedResourceRequest)
+            }
+            Self::SupportedCombinationResult => {
+                Some(OrchestratorToExplorerKind::SupportedCombinationRequest)
+            }
+            Self::GenerateResourceResponse => {
+                Some(OrchestratorToExplorerKind::GenerateResourceRequest)
+            }
+            Self::CombineResourceResponse => {
+                Some(OrchestratorToExplorerKind::CombineResourceRequest)
+            }
+            Self::BagContentResponse => Some(OrchestratorToExplorerKind::BagContentRequest),
+            Self::NeighborsRequest | Self::TravelToPlanetRequest => None,
+        }
+    }
+}
+
+/// Why an explorer could not make the resource asked by the orchestrator, sent in
+/// [`ExplorerToOrchestrator::GenerateResourceResponse`] and
+/// [`ExplorerToOrchestrator::CombineResourceResponse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceError {
+    /// The planet has no charged energy cell to make the resource.
+    NotCharged,
+    /// The planet has no recipe for the resource.
+    NoRecipe,
+    /// The planet is stopped and doesn't serve explorers.
+    PlanetStopped,
+    /// The explorer bag can't hold the resource.
+    BagFull,
+    /// The explorer doesn't have the ingredients of the complex resource.
+    MissingIngredients,
+}
+
+impl Display for ResourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceError::NotCharged => write!(f, "No charged energy cell"),
+            ResourceError::NoRecipe => write!(f, "No recipe for the resource"),
+            ResourceError::PlanetStopped => write!(f, "Planet stopped"),
+            ResourceError::BagFull => write!(f, "Bag full"),
+            ResourceError::MissingIngredients => write!(f, "Missing ingredients"),
+        }
+    }
+}
+
+impl Error for ResourceError {}
+
+impl From<GenerateError> for ResourceError {
+    fn from(err: GenerateError) -> Self {
+        match err {
+            GenerateError::MissingRecipe(_) => ResourceError::NoRecipe,
+            GenerateError::CellNotCharged => ResourceError::NotCharged,
+        }
+    }
+}
+
+impl From<CombineError> for ResourceError {
+    fn from(err: CombineError) -> Self {
+        match err {
+            CombineError::MissingRecipe(_) => ResourceError::NoRecipe,
+            CombineError::CellNotCharged => ResourceError::NotCharged,
+            CombineError::InvalidIngredients(_) => ResourceError::MissingIngredients,
+        }
+    }
+}
+
+/// Errors returned by [`travel_to_planet`] when the explorer could not move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveToPlanetError {
+    /// The orchestrator answered with no sender: the destination is not adjacent to the current
+    /// planet or it can't host the explorer (the protocol doesn't tell the two cases apart).
+    Refused {
+        ///The ID of the planet the explorer wanted to travel to
+        planet_id: ID,
+    },
+    /// The orchestrator did not answer any of the attempts in time.
+    Timeout,
+    /// The channel to or from the orchestrator is disconnected.
+    Disconnected,
+}
+
+impl Display for MoveToPlanetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveToPlanetError::Refused { planet_id } => {
+                write!(f, "Travel to planet {planet_id} refused")
+            }
+            MoveToPlanetError::Timeout => write!(f, "Travel request timed out"),
+            MoveToPlanetError::Disconnected => write!(f, "Orchestrator disconnected"),
+        }
+    }
+}
+
+impl Error for MoveToPlanetError {}
+
+/// How long [`travel_to_planet`] waits for an answer, and how many times it asks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveRetryPolicy {
+    ///How long to wait for a [`OrchestratorToExplorer::MoveToPlanet`] after each request
+    pub timeout: Duration,
+    ///How many [`ExplorerToOrchestrator::TravelToPlanetRequest`] to send before giving up
+    pub attempts: u32,
+}
+
+impl MoveRetryPolicy {
+    /// The policy every explorer is expected to use unless agreed otherwise:
+    /// 3 attempts, waiting 500 ms for each.
+    pub const DEFAULT: MoveRetryPolicy = MoveRetryPolicy {
+        timeout: Duration::from_millis(500),
+        attempts: 3,
+    };
+}
+
+impl Default for MoveRetryPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Outcome of a [`travel_to_planet`] call.
+#[derive(Debug)]
+pub struct TravelOutcome {
+    ///The [Sender] to the destination planet, or why the explorer could not move
+    pub result: Result<Sender<ExplorerToPlanet>, MoveToPlanetError>,
+    ///Messages received while waiting that are not the answer to the request, in arrival order,
+    ///so that the caller can still handle them
+    pub unrelated: Vec<OrchestratorToExplorer>,
+}
+
+/// Reference implementation of the explorer side of the travel handshake
+/// (see the *Moving to another planet (Explorer Asks)* diagram).
+///
+/// Sends a [`ExplorerToOrchestrator::TravelToPlanetRequest`] and waits up to `policy.timeout` for the
+/// [`OrchestratorToExplorer::MoveToPlanet`] answering it (the one for `dst_planet_id`), sending the
+/// request again up to `policy.attempts` times in total. Once answered, the handshake is completed with
+/// a [`ExplorerToOrchestrator::MovedToPlanetResult`], carrying `dst_planet_id` if the explorer moved and
+/// `current_planet_id` if it stayed.
+///
+/// Since a request may be answered after being retried, the orchestrator must answer duplicated
+/// requests for a move that has already been done with the same outcome, without moving the explorer again.
+/// Only the first answer is consumed here: the answers to the other copies of the request arrive later,
+/// on the caller's normal stream of messages, and should be ignored by it.
+///
+/// If the orchestrator disconnects right after answering, the answer is still returned (the
+/// explorer did move, or was refused): the caller notices the disconnection on its next exchange
+/// with the orchestrator.
+#[must_use]
+pub fn travel_to_planet<T>(
+    explorer_id: ID,
+    current_planet_id: ID,
+    dst_planet_id: ID,
+    to_orchestrator: &Sender<ExplorerToOrchestrator<T>>,
+    from_orchestrator: &Receiver<OrchestratorToExplorer>,
+    policy: MoveRetryPolicy,
+) -> TravelOutcome {
+    let mut unrelated = Vec::new();
+
+    for _ in 0..policy.attempts {
+        let request = ExplorerToOrchestrator::TravelToPlanetRequest {
+            explorer_id,
+            current_planet_id,
+            dst_planet_id,
+        };
+        if to_orchestrator.send(request).is_err() {
+            return TravelOutcome {
+                result: Err(MoveToPlanetError::Disconnected),
+                unrelated,
+            };
+        }
+
+        let deadline = Instant::now() + policy.timeout;
+        loop {
+            let msg = match from_orchestrator.recv_deadline(deadline) {
+                Ok(msg) => msg,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return TravelOutcome {
+                        result: Err(MoveToPlanetError::Disconnected),
+                        unrelated,
+                    };
+                }
+            };
+
+            match msg {
+                OrchestratorToExplorer::MoveToPlanet {
+                    sender_to_new_planet,
+                    planet_id,
+                } if planet_id == dst_planet_id => {
+                    let (planet_id, result) = match sender_to_new_planet {
+                        Some(sender) => (dst_planet_id, Ok(sender)),
+                        None => (
+                            current_planet_id,
+                            Err(MoveToPlanetError::Refused { planet_id }),
+                        ),
+                    };
+                    let ack = ExplorerToOrchestrator::MovedToPlanetResult {
+                        explorer_id,
+                        planet_id,
+                    };
+                    // a failed ack is not reported here, so that a granted sender isn't lost
+                    let _ = to_orchestrator.send(ack);
+                    return TravelOutcome { result, unrelated };
+                }
+                other => unrelated.push(other),
+            }
+        }
+    }
+
+    TravelOutcome {
+        result: Err(MoveToPlanetError::Timeout),
+        unrelated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::resource::{BasicResourceType, ComplexResourceType};
+    use crossbeam_channel::unbounded;
+    use std::thread;
+
+    const SHORT_POLICY: MoveRetryPolicy = MoveRetryPolicy {
+        timeout: Duration::from_millis(20),
+        attempts: 3,
+    };
+
+    fn move_to_planet(planet_id: ID, granted: bool) -> OrchestratorToExplorer {
+        OrchestratorToExplorer::MoveToPlanet {
+            sender_to_new_planet: granted.then(|| unbounded().0),
+            planet_id,
+        }
+    }
+
+    #[test]
+    fn travel_succeeds() {
+        let (to_orch, orch_rx) = unbounded::<ExplorerToOrchestrator<()>>();
+        let (orch_tx, from_orch) = unbounded();
+        orch_tx.send(move_to_planet(2, true)).unwrap();
+
+        let outcome = travel_to_planet(7, 1, 2, &to_orch, &from_orch, SHORT_POLICY);
+        assert!(outcome.result.is_ok());
+        assert!(outcome.unrelated.is_empty());
+
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(ExplorerToOrchestrator::TravelToPlanetRequest {
+                explorer_id: 7,
+                current_planet_id: 1,
+                dst_planet_id: 2
+            })
+        ));
+        assert!(matches!(
+            orch_rx.try_recv(),
+            Ok(ExplorerToOrchestrator::MovedToPlanetResult {
+                explorer_id: 7,
+                planet_id: 2
+            })
+        ));
+        assert!(orch_rx.is_empty());
+    }
+
+    #[test]
+    fn refusal_is_acked_with_current_planet() {
+        let (to_orch, orch_rx) = unbounded::<ExplorerToOrchestrator<()>>();
+        let (orch_tx, from_orch) = unbounded();
+        orch_tx.send(move_to_planet(2, false)).unwrap();
+
+        let outcome = travel_to_planet(7, 1, 2, &to_orch, &from_orch, SHORT_POLICY);
+        assert_eq!(
+            outcome.result.err(),
+            Some(MoveToPlanetError::Refused { planet_id: 2 })
+        );
+        assert!(matches!(
+            orch_rx.try_iter().last(),
+            Some(ExplorerToOrchestrator::MovedToPlanetResult {
+                explorer_id: 7,
+                planet_id: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn timeout_after_all_attempts() {
+        let (to_orch, orch_rx) = unbounded::<ExplorerToOrchestrator<()>>();
+        let (_orch_tx, from_orch) = unbounded();
+
+        let outcome = travel_to_planet(7, 1, 2, &to_orch, &from_orch, SHORT_POLICY);
+        assert_eq!(outcome.result.err(), Some(MoveToPlanetError::Timeout));
+
+        let sent: Vec<_> = orch_rx.try_iter().collect();
+        assert_eq!(sent.len(), 3);
+        assert!(sent.iter().all(|msg| matches!(
+            msg,
+            ExplorerToOrchestrator::TravelToPlanetRequest {
+                dst_planet_id: 2,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn unrelated_messages_are_kept_in_order() {
+        let (to_orch, _orch_rx) = unbounded::<ExplorerToOrchestrator<()>>();
+        let (orch_tx, from_orch) = unbounded();
+        orch_tx
+            .send(OrchestratorToExplorer::CurrentPlanetRequest)
+            .unwrap();
+        // the answer to another travel request is not the one waited for
+        orch_tx.send(move_to_planet(3, true)).unwrap();
+        orch_tx
+            .send(OrchestratorToExplorer::BagContentRequest)
+            .unwrap();
+        orch_tx.send(move_to_planet(2, true)).unwrap();
+        orch_tx
+            .send(OrchestratorToExplorer::StopExplorerAI)
+            .unwrap();
+
+        let outcome = travel_to_planet(7, 1, 2, &to_orch, &from_orch, SHORT_POLICY);
+        assert!(outcome.result.is_ok());
+
+        let kinds: Vec<_> = outcome
+            .unrelated
+            .iter()
+            .map(OrchestratorToExplorerKind::from)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                OrchestratorToExplorerKind::CurrentPlanetRequest,
+                OrchestratorToExplorerKind::MoveToPlanet,
+                OrchestratorToExplorerKind::BagContentRequest,
+            ]
+        );
+        // messages after the answer are left in the channel
+        assert!(matches!(
+            from_orch.try_recv(),
+            Ok(OrchestratorToExplorer::StopExplorerAI)
+        ));
+    }
+
+    #[test]
+    fn disconnections() {
+        // nobody listens to the requests
+        let (to_orch, orch_rx) = unbounded::<ExplorerToOrchestrator<()>>();
+        let (_orch_tx, from_orch) = unbounded();
+        drop(orch_rx);
+        let outcome = travel_to_planet(7, 1, 2, &to_orch, &from_orch, SHORT_POLICY);
+        assert_eq!(outcome.result.err(), Some(MoveToPlanetError::Disconnected));
+
+        // nobody answers them
+        let (to_orch, _orch_rx) = unbounded::<ExplorerToOrchestrator<()>>();
+        let (orch_tx, from_orch) = unbounded::<OrchestratorToExplorer>();
+        drop(orch_tx);
+        let outcome = travel_to_planet(7, 1, 2, &to_orch, &from_orch, SHORT_POLICY);
+        assert_eq!(outcome.result.err(), Some(MoveToPlanetError::Disconnected));
+
+        // the orchestrator leaves right after granting the move: the sender is kept
+        let (to_orch, orch_rx) = unbounded::<ExplorerToOrchestrator<()>>();
+        let (orch_tx, from_orch) = unbounded();
+        let orchestrator = thread::spawn(move || {
+            assert!(orch_rx.recv().is_ok());
+            drop(orch_rx);
+            orch_tx.send(move_to_planet(2, true)).unwrap();
+        });
+        let outcome = travel_to_planet(7, 1, 2, &to_orch, &from_orch, MoveRetryPolicy::DEFAULT);
+        orchestrator.join().unwrap();
+        assert!(outcome.result.is_ok());
+    }
+
+    #[test]
+    fn resource_error_from_generate_error() {
+        assert_eq!(
+            ResourceError::from(GenerateError::MissingRecipe(BasicResourceType::Oxygen)),
+            ResourceError::NoRecipe
+        );
+        assert_eq!(
+            ResourceError::from(GenerateError::CellNotCharged),
+            ResourceError::NotCharged
+        );
+    }
+
+    #[test]
+    fn resource_error_from_combine_error() {
+        assert_eq!(
+            ResourceError::from(CombineError::MissingRecipe(ComplexResourceType::Water)),
+            ResourceError::NoRecipe
+        );
+        assert_eq!(
+            ResourceError::from(CombineError::CellNotCharged),
+            ResourceError::NotCharged
+        );
+        assert_eq!(
+            ResourceError::from(CombineError::InvalidIngredients(ComplexResourceType::Water)),
+            ResourceError::MissingIngredients
+        );
+    }
 }
@@ -5,22 +5,66 @@
 //! For a more detailed view of the interactions between these two entities, visit the communications [diagrams](https://github.com/unitn-ap-2025/common/blob/main/MESSAGE_DIAGRAMS.md)
 
 use crate::components::asteroid::Asteroid;
-use crate::components::planet::DummyPlanetState;
+use crate::components::planet::{DummyPlanetState, StopReason};
+use crate::components::resource::{BasicResourceType, ComplexResourceType};
 use crate::components::rocket::Rocket;
 use crate::components::sunray::Sunray;
+use crate::logging::EventType;
 use crate::protocols::planet_explorer::PlanetToExplorer;
 use crate::utils::ID;
-use crossbeam_channel::Sender;
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use enum_as_inner::EnumAsInner;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use strum::VariantArray;
 use strum_macros::EnumDiscriminants;
 
 #[cfg(doc)]
-use {crate::components::energy_cell::EnergyCell, crate::components::planet::Planet};
+use {
+    crate::components::energy_cell::EnergyCell,
+    crate::components::planet::{Planet, PlanetAI},
+};
+
+/// Outcome of an [Asteroid] hitting a planet, reported in [`PlanetToOrchestrator::AsteroidAck`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsteroidOutcome {
+    /// The planet launched a rocket and deflected the asteroid
+    Deflected,
+    /// The planet had no rocket to defend itself and is destroyed
+    Destroyed,
+}
+
+impl AsteroidOutcome {
+    /// Applies the deflection rule: a single [Rocket] deflects any asteroid,
+    /// so the planet survives if and only if a rocket has been returned.
+    #[must_use]
+    pub fn from_rocket(rocket: Option<&Rocket>) -> AsteroidOutcome {
+        if rocket.is_some() {
+            AsteroidOutcome::Deflected
+        } else {
+            AsteroidOutcome::Destroyed
+        }
+    }
+}
+
+/// Why a planet terminated, reported in [`PlanetToOrchestrator::Destroyed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestroyCause {
+    /// The planet failed to deflect an [Asteroid] and destroyed itself
+    Asteroid,
+    /// The planet has been killed with a [`OrchestratorToPlanet::KillPlanet`]
+    Killed,
+}
 
 /// This enum describes all possible messages from the Orchestrator to a Planet
 #[derive(Debug, EnumAsInner, EnumDiscriminants)]
 #[strum_discriminants(name(OrchestratorToPlanetKind))]
-#[strum_discriminants(derive(Hash))]
+#[strum_discriminants(derive(
+    Hash,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::VariantArray
+))]
 pub enum OrchestratorToPlanet {
     /// This variant is used to send a [Sunray] to a planet
     ///
@@ -28,6 +72,13 @@ pub enum OrchestratorToPlanet {
     ///
     /// **Use Case**: sending a [Sunray] to charge [`EnergyCell`]
     Sunray(Sunray),
+    /// This variant is used to send many [Sunray]s to a planet at once (e.g. during a solar flare),
+    /// instead of one [`OrchestratorToPlanet::Sunray`] message each
+    ///
+    /// **Expected Response**: [`PlanetToOrchestrator::SunrayBurstAck`]
+    ///
+    /// **Use Case**: sending a burst of [Sunray]s to charge [`EnergyCell`]s in order
+    SunrayBurst(Vec<Sunray>),
     /// This variant is used to send an [Asteroid] to a planet
     ///
     /// **Expected Response**: [`PlanetToOrchestrator::AsteroidAck`]
@@ -42,17 +93,38 @@ pub enum OrchestratorToPlanet {
     StartPlanetAI,
     /// This variant is used to pause the planet Ai
     ///
-    /// **Expected Response**: [`PlanetToOrchestrator::StopPlanetAIResult`]
+    /// **Expected Response**: [`PlanetToOrchestrator::StopPlanetAIResult`],
+    /// or [`PlanetToOrchestrator::StopDeferred`] if the AI is not ready to stop
     ///
     /// **Use Case**: Freezing Planet ability to respond to every message,
     /// a planet in this state will only answer with [`PlanetToOrchestrator::Stopped`]
     StopPlanetAI,
+    /// This variant is used to reset the Planet AI, wiping its internal knowledge
+    ///
+    /// **Expected Response**: [`PlanetToOrchestrator::ResetPlanetAIResult`]
+    ///
+    /// **Use Case**: Clearing the AI-accumulated state without killing and respawning the planet,
+    /// energy cells and recipes are kept
+    ResetPlanetAI,
+    /// This variant is used to check that the planet is alive and responsive
+    ///
+    /// **Expected Response**: [`PlanetToOrchestrator::Pong`]
+    ///
+    /// **Use Case**: Detecting a hung Planet AI between heavy operations,
+    /// the planet answers even when it is in a *stopped* state
+    Ping,
     /// This variant is used to kill (or destroy) the planet
     ///
     /// **Expected Response**: [`PlanetToOrchestrator::KillPlanetResult`]
     ///
     /// **Use Case**: Instantly kill a Planet
     KillPlanet,
+    /// This variant is used to make the planet discard its rocket, without an asteroid
+    ///
+    /// **Expected Response**: [`PlanetToOrchestrator::DisarmResult`]
+    ///
+    /// **Use Case**: Reclaiming a rocket or resetting the planet defensive state
+    DisarmPlanet,
     /// This variant is used to obtain a Planet Internal State
     ///
     /// **Expected Response**: [`PlanetToOrchestrator::InternalStateResponse`]
@@ -84,7 +156,12 @@ pub enum OrchestratorToPlanet {
 /// This enum describes all possible messages from a Planet to the Orchestrator
 #[derive(Debug, EnumAsInner, EnumDiscriminants)]
 #[strum_discriminants(name(PlanetToOrchestratorKind))]
-#[strum_discriminants(derive(Hash))]
+#[strum_discriminants(derive(
+    Hash,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::VariantArray
+))]
 pub enum PlanetToOrchestrator {
     /// This variant is used to acknowledge the obtained [Sunray]
     ///
@@ -93,6 +170,17 @@ pub enum PlanetToOrchestrator {
         ///ID of the planet sending the message
         planet_id: ID,
     },
+    /// This variant is used to acknowledge a burst of [Sunray]s
+    ///
+    /// **Response to**: [`OrchestratorToPlanet::SunrayBurst`]
+    SunrayBurstAck {
+        ///ID of the planet sending the message
+        planet_id: ID,
+        ///Number of sunrays used to charge an energy cell
+        charged: usize,
+        ///Number of sunrays wasted, as no cell could absorb them
+        wasted: usize,
+    },
     /// This variant is used to acknowledge the obtained [Asteroid] and notify the orchestrator
     /// if the planet has a rocket to defend itself
     ///
@@ -102,6 +190,10 @@ pub enum PlanetToOrchestrator {
         planet_id: ID,
         ///Optional rocket returned to the Orchestrator to decide if planet can deflect the asteroid
         rocket: Option<Rocket>,
+        ///Outcome of the impact, computed by the planet from the returned rocket
+        ///(see [`AsteroidOutcome::from_rocket`]): the orchestrator should destroy the planet
+        ///if it is [`AsteroidOutcome::Destroyed`]
+        outcome: AsteroidOutcome,
     },
     /// This variant is used to acknowledge the starting of the Planet Ai
     ///
@@ -110,6 +202,18 @@ pub enum PlanetToOrchestrator {
         ///ID of the planet sending the message
         planet_id: ID,
     },
+    /// This variant is used by planets built with [`Planet::with_capabilities_announce`] to advertise
+    /// their supported resources and combinations, right after each start of the Planet AI
+    ///
+    /// **Sent after**: [`PlanetToOrchestrator::StartPlanetAIResult`], it isn't a response to any request
+    CapabilitiesAnnounce {
+        ///ID of the planet sending the message
+        planet_id: ID,
+        ///The [`BasicResourceType`] the planet can generate
+        resources: HashSet<BasicResourceType>,
+        ///The [`ComplexResourceType`] the planet can combine
+        combinations: HashSet<ComplexResourceType>,
+    },
     /// This variant is used to acknowledge the stopping of the Planet Ai, in this state a planet will only respond
     /// to incoming messages with a [`PlanetToOrchestrator::Stopped`]
     ///
@@ -118,6 +222,37 @@ pub enum PlanetToOrchestrator {
         ///ID of the planet sending the message
         planet_id: ID,
     },
+    /// This variant is used when the Planet AI is not ready to stop (see [`PlanetAI::on_stop`]),
+    /// the planet keeps running and the stop can be retried later
+    ///
+    /// **Response to**: [`OrchestratorToPlanet::StopPlanetAI`]
+    StopDeferred {
+        ///ID of the planet sending the message
+        planet_id: ID,
+    },
+    /// This variant is used to acknowledge the reset of the Planet Ai
+    ///
+    /// **Response to**: [`OrchestratorToPlanet::ResetPlanetAI`]
+    ResetPlanetAIResult {
+        ///ID of the planet sending the message
+        planet_id: ID,
+    },
+    /// This variant is used to answer a liveness check
+    ///
+    /// **Response to**: [`OrchestratorToPlanet::Ping`]
+    Pong {
+        ///ID of the planet sending the message
+        planet_id: ID,
+    },
+    /// This variant is used to hand the planet rocket back to the orchestrator after a disarm
+    ///
+    /// **Response to**: [`OrchestratorToPlanet::DisarmPlanet`]
+    DisarmResult {
+        ///ID of the planet sending the message
+        planet_id: ID,
+        ///The rocket taken out of the planet, [None] if it had none
+        rocket: Option<Rocket>,
+    },
     /// This variant is used to acknowledge the killing of a planet, in this case the planet thread will be terminated
     /// and the planet will be deleted from the galaxy
     ///
@@ -144,7 +279,8 @@ pub enum PlanetToOrchestrator {
         ///
         /// [Ok] if the [Sender] to the incoming explorer has been correctly set up
         ///
-        /// [Err(String)] if an error occurred
+        /// [Err(String)] if an error occurred or the planet refused the explorer (e.g. it's full),
+        /// in that case the explorer must stay where it is
         res: Result<(), String>,
     },
     /// This variant is used to acknowledge that an explorer is leaving the planet
@@ -163,10 +299,37 @@ pub enum PlanetToOrchestrator {
         res: Result<(), String>,
     },
     /// This variant is used by planets that are currently in a *stopped* state
-    /// to acknowledge any message coming from the Orchestrator (except for [`OrchestratorToPlanet::StartPlanetAI`])
+    /// to acknowledge any message coming from the Orchestrator (except for [`OrchestratorToPlanet::StartPlanetAI`],
+    /// [`OrchestratorToPlanet::KillPlanet`] and [`OrchestratorToPlanet::Ping`])
     Stopped {
         ///ID of the planet sending the message
         planet_id: ID,
+        ///Why the planet is stopped: [`StopReason::NeverStarted`] before the first start,
+        ///[`StopReason::ManualStop`] after a [`OrchestratorToPlanet::StopPlanetAI`]
+        reason: StopReason,
+    },
+    /// This variant is used by a planet to explicitly decline a request it can't fulfill,
+    /// instead of sending the expected response (see [`PlanetAI::decline_request`])
+    ///
+    /// **Response to**: the request of kind `request_kind`
+    Nack {
+        ///ID of the planet sending the message
+        planet_id: ID,
+        ///The kind of the declined request
+        request_kind: OrchestratorToPlanetKind,
+        ///Why the request has been declined
+        reason: String,
+    },
+    /// This variant is used by planets built with [`Planet::with_self_destruct`] to notify that
+    /// the planet thread is terminating, telling whether it was hit by an asteroid or killed
+    ///
+    /// **Sent after**: the [`PlanetToOrchestrator::AsteroidAck`] of a failed defense or the
+    /// [`PlanetToOrchestrator::KillPlanetResult`], it isn't a response to any request
+    Destroyed {
+        ///ID of the planet sending the message
+        planet_id: ID,
+        ///Why the planet terminated
+        cause: DestroyCause,
     },
 }
 impl PlanetToOrchestrator {
@@ -176,14 +339,176 @@ impl PlanetToOrchestrator {
     pub fn planet_id(&self) -> ID {
         match self {
             PlanetToOrchestrator::SunrayAck { planet_id, .. }
+            | PlanetToOrchestrator::SunrayBurstAck { planet_id, .. }
             | PlanetToOrchestrator::AsteroidAck { planet_id, .. }
             | PlanetToOrchestrator::StartPlanetAIResult { planet_id, .. }
+            | PlanetToOrchestrator::CapabilitiesAnnounce { planet_id, .. }
             | PlanetToOrchestrator::StopPlanetAIResult { planet_id, .. }
+            | PlanetToOrchestrator::StopDeferred { planet_id, .. }
+            | PlanetToOrchestrator::ResetPlanetAIResult { planet_id, .. }
+            | PlanetToOrchestrator::Pong { planet_id, .. }
+            | PlanetToOrchestrator::DisarmResult { planet_id, .. }
             | PlanetToOrchestrator::KillPlanetResult { planet_id, .. }
             | PlanetToOrchestrator::InternalStateResponse { planet_id, .. }
             | PlanetToOrchestrator::IncomingExplorerResponse { planet_id, .. }
             | PlanetToOrchestrator::OutgoingExplorerResponse { planet_id, .. }
-            | PlanetToOrchestrator::Stopped { planet_id, .. } => *planet_id,
+            | PlanetToOrchestrator::Stopped { planet_id, .. }
+            | PlanetToOrchestrator::Nack { planet_id, .. }
+            | PlanetToOrchestrator::Destroyed { planet_id, .. } => *planet_id,
+        }
+    }
+}
+
+impl OrchestratorToPlanetKind {
+    /// Returns every kind of message, in declaration order
+    /// (e.g. to check that a handler exists for each of them).
+    #[must_use]
+    pub fn all() -> &'static [OrchestratorToPlanetKind] {
+        Self::VARIANTS
+    }
+
+    /// Returns the [`EventType`] to use when logging a message of this kind.
+    #[must_use]
+    pub fn event_type(&self) -> EventType {
+        EventType::MessageOrchestratorToPlanet
+    }
+
+    /// Returns the kind of the response a planet is expected to send to a message of this kind,
+    /// or [None] if no response is expected.
+    ///
+    /// A *stopped* planet answers most messages with [`PlanetToOrchestrator::Stopped`] instead,
+    /// and a stop can be answered with [`PlanetToOrchestrator::StopDeferred`].
+    #[must_use]
+    pub fn expected_response(&self) -> Option<PlanetToOrchestratorKind> {
+        match self {
+            Self::Sunray => Some(PlanetToOrchestratorKind::SunrayAck),
+            Self::SunrayBurst => Some(PlanetToOrchestratorKind::SunrayBurstAck),
+            Self::Asteroid => Some(PlanetToOrchestratorKind::AsteroidAck),
+            Self::StartPlanetAI => Some(PlanetToOrchestratorKind::StartPlanetAIResult),
+            Self::StopPlanetAI => Some(PlanetToOrchestratorKind::StopPlanetAIResult),
+            Self::ResetPlanetAI => Some(PlanetToOrchestratorKind::ResetPlanetAIResult),
+            Self::Ping => Some(PlanetToOrchestratorKind::Pong),
+            Self::KillPlanet => Some(PlanetToOrchestratorKind::KillPlanetResult),
+            Self::DisarmPlanet => Some(PlanetToOrchestratorKind::DisarmResult),
+            Self::InternalStateRequest => Some(PlanetToOrchestratorKind::InternalStateResponse),
+            Self::IncomingExplorerRequest => {
+                Some(PlanetToOrchestratorKind::IncomingExplorerResponse)
+            }
+            Self::OutgoingExplorerRequest => {
+                Some(PlanetToOrchestratorKind::OutgoingExplorerResponse)
+            }
+        }
+    }
+}
+
+impl PlanetToOrchestratorKind {
+    /// Returns every kind of message, in declaration order
+    /// (e.g. to check that a handler exists for each of them).
+    #[must_use]
+    pub fn all() -> &'static [PlanetToOrchestratorKind] {
+        Self::VARIANTS
+    }
+
+    /// Returns the [`EventType`] to use when logging a message of this kind.
+    #[must_use]
+    pub fn event_type(&self) -> EventType {
+        EventType::MessagePlanetToOrchestrator
+    }
+
+    /// Returns the kind of the request a message of this kind is a response to,
+    /// or [None] if it can answer any request (like [`PlanetToOrchestrator::Stopped`])
+    /// or isn't a response at all (like [`PlanetToOrchestrator::CapabilitiesAnnounce`]).
+    #[must_use]
+    pub fn responds_to(&self) -> Option<OrchestratorToPlanetKind> {
+        match self {
+            Self::SunrayAck => Some(OrchestratorToPlanetKind::Sunray),
+            Self::SunrayBurstAck => Some(OrchestratorToPlanetKind::SunrayBurst),
+            Self::AsteroidAck => Some(OrchestratorToPlanetKind::Asteroid),
+            Self::StartPlanetAIResult => Some(OrchestratorToPlanetKind::StartPlanetAI),
+            Self::StopPlanetAIResult | Self::StopDeferred => {
+                Some(OrchestratorToPlanetKind::StopPlanetAI)
+            }
+            Self::ResetPlanetAIResult => Some(OrchestratorToPlanetKind::ResetPlanetAI),
+            Self::Pong => Some(OrchestratorToPlanetKind::Ping),
+            Self::KillPlanetResult => Some(OrchestratorToPlanetKind::KillPlanet),
+            Self::DisarmResult => Some(OrchestratorToPlanetKind::DisarmPlanet),
+            Self::InternalStateResponse => Some(OrchestratorToPlanetKind::InternalStateRequest),
+            Self::IncomingExplorerResponse => {
+                Some(OrchestratorToPlanetKind::IncomingExplorerRequest)
+            }
+            Self::OutgoingExplorerResponse => {
+                Some(OrchestratorToPlanetKind::OutgoingExplorerRequest)
+            }
+            // a nack carries the kind of the declined request
+            Self::Stopped | Self::CapabilitiesAnnounce | Self::Nack | Self::Destroyed => None,
+        }
+    }
+}
+
+/// Outcome of a [`broadcast_to_planets`] call.
+#[derive(Debug, Default)]
+pub struct BroadcastAcks {
+    ///The response of each planet that answered the broadcast, by planet id
+    pub acks: HashMap<ID, PlanetToOrchestrator>,
+    ///The planets whose channel is disconnected, the message could not be sent to them
+    pub unreachable: HashSet<ID>,
+    ///The planets that did not answer before the timeout expired
+    pub timed_out: HashSet<ID>,
+    ///Messages received while waiting that are not a response to the broadcast, in arrival order,
+    ///so that the caller can still handle them
+    pub unrelated: Vec<PlanetToOrchestrator>,
+}
+
+/// Sends a message to every planet in `planets` (e.g. a `&HashMap<ID, Sender<OrchestratorToPlanet>>`), then collects their responses from `from_planets`
+/// (the receiver shared by all planets) until all of them answered or `timeout` expired.
+///
+/// Since messages can't be cloned (they may hold channels or resources), `msg_fn` is called once
+/// per planet to build a fresh message: it should always build the same variant.
+///
+/// A response is collected for a planet if its kind [responds to](PlanetToOrchestratorKind::responds_to)
+/// the broadcast message kind, if it is a [`PlanetToOrchestrator::Nack`] declining the broadcast
+/// message or if it is a [`PlanetToOrchestrator::Stopped`]: any other message is handed back in
+/// [`BroadcastAcks::unrelated`].
+pub fn broadcast_to_planets<'a>(
+    planets: impl IntoIterator<Item = (&'a ID, &'a Sender<OrchestratorToPlanet>)>,
+    from_planets: &Receiver<PlanetToOrchestrator>,
+    msg_fn: impl Fn() -> OrchestratorToPlanet,
+    timeout: Duration,
+) -> BroadcastAcks {
+    let mut result = BroadcastAcks::default();
+    let mut pending = HashSet::new();
+    let mut sent_kind = None;
+
+    for (id, sender) in planets {
+        let msg = msg_fn();
+        let kind = OrchestratorToPlanetKind::from(&msg);
+        if sender.send(msg).is_ok() {
+            sent_kind = Some(kind);
+            pending.insert(*id);
+        } else {
+            result.unreachable.insert(*id);
         }
     }
+
+    let deadline = Instant::now() + timeout;
+    while !pending.is_empty() {
+        let msg = match from_planets.recv_deadline(deadline) {
+            Ok(msg) => msg,
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => break,
+        };
+
+        let is_ack = match &msg {
+            PlanetToOrchestrator::Stopped { .. } => true,
+            PlanetToOrchestrator::Nack { request_kind, .. } => Some(*request_kind) == sent_kind,
+            _ => PlanetToOrchestratorKind::from(&msg).responds_to() == sent_kind,
+        };
+        if is_ack && pending.remove(&msg.planet_id()) {
+            result.acks.insert(msg.planet_id(), msg);
+        } else {
+            result.unrelated.push(msg);
+        }
+    }
+
+    result.timed_out = pending;
+    result
 }
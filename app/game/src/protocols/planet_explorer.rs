@@ -4,13 +4,16 @@
 //! between the Planets and the Explorers
 //! For a more detailed view of the interactions between these two entities, visit the communications [diagrams](https://github.com/unitn-ap-2025/common/blob/main/MESSAGE_DIAGRAMS.md)
 
+use crate::components::planet::{PlanetState, StopReason};
 use crate::components::resource::{
-    BasicResource, BasicResourceType, ComplexResource, ComplexResourceRequest, ComplexResourceType,
-    GenericResource,
+    BasicResource, BasicResourceType, Combinator, CombineError, ComplexResource,
+    ComplexResourceRequest, ComplexResourceType, Generator, GenericResource, ResourceType,
 };
+use crate::logging::EventType;
 use crate::utils::ID;
 use enum_as_inner::EnumAsInner;
 use std::collections::HashSet;
+use strum::VariantArray;
 use strum_macros::EnumDiscriminants;
 
 #[cfg(doc)]
@@ -19,7 +22,12 @@ use crate::components::energy_cell::EnergyCell;
 /// This enum describes all possible messages from an Explorer to a Planet.
 #[derive(Debug, EnumAsInner, EnumDiscriminants)]
 #[strum_discriminants(name(ExplorerToPlanetKind))]
-#[strum_discriminants(derive(Hash))]
+#[strum_discriminants(derive(
+    Hash,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::VariantArray
+))]
 pub enum ExplorerToPlanet {
     /// This variant is used to ask the Planet for the available [`BasicResourceType`]
     ///
@@ -70,6 +78,36 @@ pub enum ExplorerToPlanet {
         ///The ID of the Explorer sending the message
         explorer_id: ID,
     },
+    /// This variant is used to ask the Planet to store a resource carried by the Explorer
+    ///
+    /// **Expected Response**: [`PlanetToExplorer::DepositResourceResponse`]
+    ///
+    /// **Use Case**: Dropping a resource on the planet storage, the Planet AI decides whether to accept it
+    DepositResourceRequest {
+        ///The ID of the Explorer sending the message
+        explorer_id: ID,
+        ///The resource to be deposited
+        resource: GenericResource,
+    },
+    /// This variant is used to ask the Planet for the resources it currently stores
+    ///
+    /// **Expected Response**: [`PlanetToExplorer::StoredResourcesResponse`]
+    ///
+    /// **Use Case**: Deciding whether to request a deposit or a generation
+    StoredResourcesRequest {
+        ///The ID of the Explorer sending the message
+        explorer_id: ID,
+    },
+    /// This variant is used to ask the Planet for the [`ComplexResourceType`] it can combine right now
+    /// for the explorer, considering both its recipes and its charged [`EnergyCell`]s
+    ///
+    /// **Expected Response**: [`PlanetToExplorer::AffordableCombinationsResponse`]
+    ///
+    /// **Use Case**: Avoiding a combination request that would fail for lack of energy
+    AffordableCombinationsRequest {
+        ///The ID of the Explorer sending the message
+        explorer_id: ID,
+    },
 }
 
 impl ExplorerToPlanet {
@@ -82,7 +120,10 @@ impl ExplorerToPlanet {
             | ExplorerToPlanet::SupportedCombinationRequest { explorer_id, .. }
             | ExplorerToPlanet::GenerateResourceRequest { explorer_id, .. }
             | ExplorerToPlanet::CombineResourceRequest { explorer_id, .. }
-            | ExplorerToPlanet::AvailableEnergyCellRequest { explorer_id, .. } => *explorer_id,
+            | ExplorerToPlanet::AvailableEnergyCellRequest { explorer_id, .. }
+            | ExplorerToPlanet::DepositResourceRequest { explorer_id, .. }
+            | ExplorerToPlanet::StoredResourcesRequest { explorer_id, .. }
+            | ExplorerToPlanet::AffordableCombinationsRequest { explorer_id, .. } => *explorer_id,
         }
     }
 }
@@ -90,16 +131,23 @@ impl ExplorerToPlanet {
 /// This enum describes all possible messages from a Planet to an Explorer.
 #[derive(Debug, EnumAsInner, EnumDiscriminants)]
 #[strum_discriminants(name(PlanetToExplorerKind))]
-#[strum_discriminants(derive(Hash))]
+#[strum_discriminants(derive(
+    Hash,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::VariantArray
+))]
 pub enum PlanetToExplorer {
-    /// This variant is used to send the available [`BasicResourceType`] list to the Explorer
+    /// This variant is used to send the available [`BasicResourceType`] list to the Explorer,
+    /// build it with [`PlanetToExplorer::supported_resources_from`]
     ///
     /// **Response To**: [`ExplorerToPlanet::SupportedResourceRequest`]
     SupportedResourceResponse {
         ///The list of available [`BasicResourceType`]
         resource_list: HashSet<BasicResourceType>,
     },
-    /// This variant is used to send the available [`ComplexResourceType`] list to the Explorer
+    /// This variant is used to send the available [`ComplexResourceType`] list to the Explorer,
+    /// build it with [`PlanetToExplorer::supported_combinations_from`]
     ///
     /// **Response To**: [`ExplorerToPlanet::SupportedCombinationRequest`]
     SupportedCombinationResponse {
@@ -125,9 +173,9 @@ pub enum PlanetToExplorer {
         ///
         ///[Ok(ComplexResource)] if complex resource has been crafted correctly
         ///
-        ///An [Err] triplet containing an error String and the two resources that were intended to be combined that are given
+        ///An [Err] triplet containing a [`CombineError`] and the two resources that were intended to be combined that are given
         ///back to the Explorer
-        complex_response: Result<ComplexResource, (String, GenericResource, GenericResource)>,
+        complex_response: Result<ComplexResource, (CombineError, GenericResource, GenericResource)>,
     },
     /// This variant is used to send the number of available energy cells to the Explorer
     ///
@@ -136,7 +184,208 @@ pub enum PlanetToExplorer {
         ///The number of charged cells available
         available_cells: ID,
     },
+    /// This variant is used to answer a resource deposit
+    ///
+    /// **Response To**: [`ExplorerToPlanet::DepositResourceRequest`]
+    DepositResourceResponse {
+        ///Result of the deposit:
+        ///
+        ///[Ok] if the resource has been accepted and stored by the planet
+        ///
+        ///[Err(GenericResource)] containing the rejected resource, given back to the Explorer
+        res: Result<(), GenericResource>,
+    },
+    /// This variant is used to send the types of the resources stored on the planet,
+    /// build it with [`PlanetToExplorer::stored_resources_from`]
+    ///
+    /// **Response To**: [`ExplorerToPlanet::StoredResourcesRequest`]
+    StoredResourcesResponse {
+        ///The type of each stored resource, one entry per resource
+        resources: Vec<ResourceType>,
+    },
+    /// This variant is used to send the [`ComplexResourceType`] the planet can currently combine,
+    /// build it with [`PlanetToExplorer::affordable_combinations_from`]
+    ///
+    /// **Response To**: [`ExplorerToPlanet::AffordableCombinationsRequest`]
+    AffordableCombinationsResponse {
+        ///The complex resources the planet has a recipe for, if it has a charged cell to combine
+        ///them (empty otherwise)
+        affordable: HashSet<ComplexResourceType>,
+    },
     /// This variant is used by planets that are currently in a *stopped* state
     /// to acknowledge any message coming from an explorer
-    Stopped,
+    Stopped {
+        ///Why the planet is stopped (see [`StopReason`])
+        reason: StopReason,
+    },
+}
+
+impl PlanetToExplorer {
+    /// Builds the canonical [`PlanetToExplorer::SupportedResourceResponse`]
+    /// for a planet with the given [`Generator`]: all of its recipes.
+    #[must_use]
+    pub fn supported_resources_from(generator: &Generator) -> PlanetToExplorer {
+        PlanetToExplorer::SupportedResourceResponse {
+            resource_list: generator.all_available_recipes(),
+        }
+    }
+
+    /// Builds the canonical [`PlanetToExplorer::SupportedCombinationResponse`]
+    /// for a planet with the given [`Combinator`]: all of its recipes.
+    #[must_use]
+    pub fn supported_combinations_from(combinator: &Combinator) -> PlanetToExplorer {
+        PlanetToExplorer::SupportedCombinationResponse {
+            combination_list: combinator.all_available_recipes(),
+        }
+    }
+
+    /// Builds the canonical [`PlanetToExplorer::StoredResourcesResponse`]
+    /// for a planet with the given [`PlanetState`]: the type of every stored resource.
+    #[must_use]
+    pub fn stored_resources_from(state: &PlanetState) -> PlanetToExplorer {
+        PlanetToExplorer::StoredResourcesResponse {
+            resources: state
+                .resources_iter()
+                .map(GenericResource::get_type)
+                .collect(),
+        }
+    }
+
+    /// Returns a compact, single-line description of the response for logs, e.g.
+    /// `"GenerateResourceResponse(Oxygen)"` or `"AvailableEnergyCellResponse(3)"`.
+    ///
+    /// It is made of the message kind followed by the outcome of the request:
+    /// collections are summarized by their size and failures by their error variant.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let kind = PlanetToExplorerKind::from(self);
+        match self {
+            PlanetToExplorer::SupportedResourceResponse { resource_list } => {
+                format!("{kind}({} recipes)", resource_list.len())
+            }
+            PlanetToExplorer::SupportedCombinationResponse {
+                combination_list: set,
+            }
+            | PlanetToExplorer::AffordableCombinationsResponse { affordable: set } => {
+                format!("{kind}({} recipes)", set.len())
+            }
+            PlanetToExplorer::GenerateResourceResponse { resource } => match resource {
+                Some(resource) => format!("{kind}({})", resource.get_type().name()),
+                None => format!("{kind}(failed)"),
+            },
+            PlanetToExplorer::CombineResourceResponse { complex_response } => {
+                match complex_response {
+                    Ok(resource) => format!("{kind}({})", resource.get_type().name()),
+                    Err((err, ..)) => format!("{kind}({err:?})"),
+                }
+            }
+            PlanetToExplorer::AvailableEnergyCellResponse { available_cells } => {
+                format!("{kind}({available_cells})")
+            }
+            PlanetToExplorer::DepositResourceResponse { res } => match res {
+                Ok(()) => format!("{kind}(accepted)"),
+                Err(resource) => format!("{kind}(rejected {})", resource.get_type().name()),
+            },
+            PlanetToExplorer::StoredResourcesResponse { resources } => {
+                format!("{kind}({} resources)", resources.len())
+            }
+            PlanetToExplorer::Stopped { reason } => format!("{kind}({reason:?})"),
+        }
+    }
+
+    /// Builds the canonical [`PlanetToExplorer::AffordableCombinationsResponse`]
+    /// for a planet with the given [`PlanetState`] and [`Combinator`]: all the recipes
+    /// of the planet if it has at least one charged cell, none otherwise.
+    ///
+    /// The explorer brings its own ingredients in a [`ExplorerToPlanet::CombineResourceRequest`],
+    /// so each combination only costs the planet one charged cell.
+    #[must_use]
+    pub fn affordable_combinations_from(
+        state: &PlanetState,
+        combinator: &Combinator,
+    ) -> PlanetToExplorer {
+        let affordable = if state.charged_cells_count() >= 1 {
+            combinator.all_available_recipes()
+        } else {
+            HashSet::new()
+        };
+        PlanetToExplorer::AffordableCombinationsResponse { affordable }
+    }
+}
+
+impl ExplorerToPlanetKind {
+    /// Returns every kind of message, in declaration order
+    /// (e.g. to check that a handler exists for each of them).
+    #[must_use]
+    pub fn all() -> &'static [ExplorerToPlanetKind] {
+        Self::VARIANTS
+    }
+
+    /// Returns the [`EventType`] to use when logging a message of this kind.
+    #[must_use]
+    pub fn event_type(&self) -> EventType {
+        EventType::MessageExplorerToPlanet
+    }
+
+    /// Returns the kind of the response a planet is expected to send to a message of this kind,
+    /// or [None] if no response is expected.
+    ///
+    /// A *stopped* planet answers any message with [`PlanetToExplorer::Stopped`] instead.
+    #[must_use]
+    pub fn expected_response(&self) -> Option<PlanetToExplorerKind> {
+        match self {
+            Self::SupportedResourceRequest => Some(PlanetToExplorerKind::SupportedResourceResponse),
+            Self::SupportedCombinationRequest => {
+                Some(PlanetToExplorerKind::SupportedCombinationResponse)
+            }
+            Self::GenerateResourceRequest => Some(PlanetToExplorerKind::GenerateResourceResponse),
+            Self::CombineResourceRequest => Some(PlanetToExplorerKind::CombineResourceResponse),
+            Self::AvailableEnergyCellRequest => {
+                Some(PlanetToExplorerKind::AvailableEnergyCellResponse)
+            }
+            Self::DepositResourceRequest => Some(PlanetToExplorerKind::DepositResourceResponse),
+            Self::StoredResourcesRequest => Some(PlanetToExplorerKind::StoredResourcesResponse),
+            Self::AffordableCombinationsRequest => {
+                Some(PlanetToExplorerKind::AffordableCombinationsResponse)
+            }
+        }
+    }
+}
+
+impl PlanetToExplorerKind {
+    /// Returns every kind of message, in declaration order
+    /// (e.g. to check that a handler exists for each of them).
+    #[must_use]
+    pub fn all() -> &'static [PlanetToExplorerKind] {
+        Self::VARIANTS
+    }
+
+    /// Returns the [`EventType`] to use when logging a message of this kind.
+    #[must_use]
+    pub fn event_type(&self) -> EventType {
+        EventType::MessagePlanetToExplorer
+    }
+
+    /// Returns the kind of the request a message of this kind is a response to,
+    /// or [None] if it can answer any request (like [`PlanetToExplorer::Stopped`]).
+    #[must_use]
+    pub fn responds_to(&self) -> Option<ExplorerToPlanetKind> {
+        match self {
+            Self::SupportedResourceResponse => Some(ExplorerToPlanetKind::SupportedResourceRequest),
+            Self::SupportedCombinationResponse => {
+                Some(ExplorerToPlanetKind::SupportedCombinationRequest)
+            }
+            Self::GenerateResourceResponse => Some(ExplorerToPlanetKind::GenerateResourceRequest),
+            Self::CombineResourceResponse => Some(ExplorerToPlanetKind::CombineResourceRequest),
+            Self::AvailableEnergyCellResponse => {
+                Some(ExplorerToPlanetKind::AvailableEnergyCellRequest)
+            }
+            Self::DepositResourceResponse => Some(ExplorerToPlanetKind::DepositResourceRequest),
+            Self::StoredResourcesResponse => Some(ExplorerToPlanetKind::StoredResourcesRequest),
+            Self::AffordableCombinationsResponse => {
+                Some(ExplorerToPlanetKind::AffordableCombinationsRequest)
+            }
+            Self::Stopped => None,
+        }
+    }
 }
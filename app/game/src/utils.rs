@@ -2,3 +2,83 @@
 
 ///ID type to identify planets and explorers
 pub type ID = u32;
+
+/// A small seedable pseudo-random number generator, to share a reproducible random source
+/// among the actors of a simulation (e.g. passed to the planet AIs at construction).
+///
+/// Two `Rng`s built with the same seed produce the same sequence of numbers, which makes
+/// simulations replayable. It is a plain xorshift generator: fast and dependency-free, but
+/// **not** cryptographically secure.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a new `Rng` from a seed. Any seed is valid, `0` included.
+    #[must_use]
+    pub fn seeded(seed: u64) -> Self {
+        // scramble the seed (splitmix64 finalizer), as xorshift is stuck on a zero state
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        Self {
+            state: if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z },
+        }
+    }
+
+    /// Returns the next random `u64` of the sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        // xorshift64*
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns the next random `u32` of the sequence.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn next_u32(&mut self) -> u32 {
+        // the upper bits are the most random ones
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Returns a random element of `items`, or [None] if it is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        let len = u64::try_from(items.len()).ok().filter(|&len| len > 0)?;
+        let i = usize::try_from(self.next_u64() % len).ok()?;
+        items.get(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::seeded(42);
+        let mut b = Rng::seeded(42);
+        let mut c = Rng::seeded(0);
+
+        let seq_a: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        let seq_b: Vec<u64> = (0..10).map(|_| b.next_u64()).collect();
+        let seq_c: Vec<u64> = (0..10).map(|_| c.next_u64()).collect();
+        assert_eq!(seq_a, seq_b);
+        assert_ne!(seq_a, seq_c);
+        assert!(seq_c.iter().any(|&n| n != 0));
+    }
+
+    #[test]
+    fn choose_picks_from_the_slice() {
+        let mut rng = Rng::seeded(7);
+        let items = [1, 2, 3];
+
+        assert_eq!(rng.choose::<u8>(&[]), None);
+        for _ in 0..20 {
+            assert!(items.contains(rng.choose(&items).unwrap()));
+        }
+    }
+}